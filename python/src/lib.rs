@@ -0,0 +1,243 @@
+//! Word Quest as a reinforcement learning environment, with a Gym-style
+//! API over the game's own rules.
+//!
+//! Actions are numbers: `DISCARD`, `SHUFFLE`, or `FIRST_WORD + i` to play
+//! the `i`th word of `Env.words`. `Env.action_mask()` says which of them
+//! can be taken.
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+
+use ci210_word_game::i18n::{self, Language};
+use ci210_word_game::json;
+use ci210_word_game::mode::GameMode;
+use ci210_word_game::rules::{self, Move, Outcome, Ruleset, Run};
+use ci210_word_game::unlocks::Loadout;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+const DISCARD: usize = 0;
+const SHUFFLE: usize = 1;
+const FIRST_WORD: usize = 2;
+
+/// Episodes that haven't ended after this many steps are cut off, e.g. an
+/// agent that only shuffles.
+const MAX_STEPS: u32 = 1000;
+
+/// One run at a time of Word Quest.
+#[pyclass]
+struct Env {
+    language: &'static Language,
+    words_db: Arc<HashSet<String>>,
+    /// The dictionary in alphabetical order, which fixes the action numbers.
+    words: Vec<String>,
+    mode: GameMode,
+    ruleset: Ruleset,
+    run: Run,
+    steps: u32,
+    done: bool,
+}
+
+impl Env {
+    fn new_run(&self, seed: Option<u64>) -> Run {
+        let mut run = Run::new(self.mode, self.language, self.words_db.clone(), self.ruleset.clone(),
+                               Loadout::default(), false);
+        if let Some(seed) = seed {
+            run.set_seed(seed);
+        }
+        run.deal();
+        run
+    }
+
+    fn observation<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let observation = self.run.observe();
+        let dict = PyDict::new(py);
+        dict.set_item("rack", observation.rack.iter().map(char::to_string).collect::<Vec<_>>())?;
+        dict.set_item("target", observation.target)?;
+        dict.set_item("round_score", observation.round_score)?;
+        dict.set_item("run_score", observation.run_score)?;
+        dict.set_item("words_remaining", observation.words_remaining)?;
+        dict.set_item("discards", observation.discards)?;
+        dict.set_item("round", observation.round)?;
+        dict.set_item("guessed_words", observation.guessed_words)?;
+        dict.set_item("seed", observation.seed)?;
+        Ok(dict)
+    }
+
+    fn action_move(&self, action: usize) -> PyResult<Move> {
+        match action {
+            DISCARD => Ok(Move::Discard),
+            SHUFFLE => Ok(Move::Shuffle),
+            _ => self.words.get(action - FIRST_WORD)
+                .map(|word| Move::Play { word: word.clone() })
+                .ok_or_else(|| PyValueError::new_err(format!("No action {}", action))),
+        }
+    }
+}
+
+#[pymethods]
+impl Env {
+    /// `rules` is the path of a ruleset file and `dictionary` the path of a
+    /// dictionary to use instead of the language's own.
+    #[new]
+    #[pyo3(signature = (language = "English", mode = "standard", rules = None, dictionary = None))]
+    fn new(language: &str, mode: &str, rules: Option<&str>, dictionary: Option<&str>) -> PyResult<Env> {
+        let language = i18n::find(language)
+            .ok_or_else(|| PyValueError::new_err(format!("Unknown language: {}", language)))?;
+        let mode: GameMode = mode.parse().map_err(PyValueError::new_err)?;
+        let ruleset = match rules {
+            Some(path) => Ruleset::load(Path::new(path)).map_err(PyValueError::new_err)?,
+            None => Ruleset::default(),
+        };
+        let words_db: HashSet<String> = match dictionary {
+            Some(path) => json::get_available_words(path).map_err(PyValueError::new_err)?.into_iter().collect(),
+            None => rules::initialize_words_db(language).map_err(PyValueError::new_err)?,
+        };
+        let mut words: Vec<String> = words_db.iter().cloned().collect();
+        words.sort();
+
+        let words_db = Arc::new(words_db);
+        let run = Run::new(mode, language, words_db.clone(), ruleset.clone(), Loadout::default(), false);
+        let mut env = Env { language, words_db, words, mode, ruleset, run, steps: 0, done: false };
+        env.run = env.new_run(None);
+        Ok(env)
+    }
+
+    /// Starts a new run and returns its first observation.
+    #[pyo3(signature = (seed = None))]
+    fn reset<'py>(&mut self, py: Python<'py>, seed: Option<u64>) -> PyResult<Bound<'py, PyDict>> {
+        self.run = self.new_run(seed);
+        self.steps = 0;
+        self.done = false;
+        self.observation(py)
+    }
+
+    /// Takes an action. Returns the observation, the points it scored,
+    /// whether the run is over, and an info dict with the round and run
+    /// outcome.
+    fn step<'py>(&mut self, py: Python<'py>, action: usize)
+                 -> PyResult<(Bound<'py, PyDict>, i32, bool, Bound<'py, PyDict>)> {
+        if self.done {
+            return Err(PyValueError::new_err("The run is over, call reset()"));
+        }
+        let action = self.action_move(action)?;
+        let reward = self.run.make_move(&action).unwrap_or(0);
+        let outcome = self.run.check();
+        self.run.deal();
+        self.steps += 1;
+
+        let truncated = self.steps >= MAX_STEPS;
+        self.done = matches!(outcome, Outcome::RunWon | Outcome::RunLost) || truncated;
+        let info = PyDict::new(py);
+        info.set_item("round_won", matches!(outcome, Outcome::RoundWon | Outcome::RunWon))?;
+        info.set_item("won", outcome == Outcome::RunWon)?;
+        info.set_item("truncated", truncated)?;
+        Ok((self.observation(py)?, reward, self.done, info))
+    }
+
+    /// One flag per action: whether it plays a word that scores right now,
+    /// or for `DISCARD` whether a discard is left. Shuffling is always allowed.
+    fn action_mask(&self) -> Vec<bool> {
+        let mut mask = Vec::with_capacity(FIRST_WORD + self.words.len());
+        mask.push(self.run.discards > 0);
+        mask.push(true);
+        mask.extend(self.words.iter().map(|word| self.run.can_build(word) && self.run.is_valid_word(word)));
+        mask
+    }
+
+    /// The words that actions from `FIRST_WORD` on play, in order.
+    #[getter]
+    fn words(&self) -> Vec<String> {
+        self.words.clone()
+    }
+
+    #[getter]
+    fn action_count(&self) -> usize {
+        FIRST_WORD + self.words.len()
+    }
+}
+
+/// Several environments stepped together. A run that ends is reset right
+/// away, so every step returns an observation for each environment.
+#[pyclass]
+struct VecEnv {
+    envs: Vec<Env>,
+}
+
+#[pymethods]
+impl VecEnv {
+    #[new]
+    #[pyo3(signature = (count, language = "English", mode = "standard", rules = None, dictionary = None))]
+    fn new(count: usize, language: &str, mode: &str, rules: Option<&str>,
+           dictionary: Option<&str>) -> PyResult<VecEnv> {
+        let first = Env::new(language, mode, rules, dictionary)?;
+        // The environments share the first one's dictionary rather than each loading it.
+        let envs = (0..count)
+            .map(|_| Env {
+                language: first.language,
+                words_db: first.words_db.clone(),
+                words: first.words.clone(),
+                mode: first.mode,
+                ruleset: first.ruleset.clone(),
+                run: first.new_run(None),
+                steps: 0,
+                done: false,
+            })
+            .collect();
+        Ok(VecEnv { envs })
+    }
+
+    /// Resets every environment. Seeds, if given, go one per environment.
+    #[pyo3(signature = (seeds = None))]
+    fn reset<'py>(&mut self, py: Python<'py>, seeds: Option<Vec<u64>>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        if seeds.as_ref().is_some_and(|seeds| seeds.len() != self.envs.len()) {
+            return Err(PyValueError::new_err("Need one seed per environment"));
+        }
+        self.envs.iter_mut().enumerate()
+            .map(|(i, env)| env.reset(py, seeds.as_ref().map(|seeds| seeds[i])))
+            .collect()
+    }
+
+    /// Steps every environment with its action. Returns lists of
+    /// observations, rewards, done flags and info dicts.
+    #[allow(clippy::type_complexity)]
+    fn step<'py>(&mut self, py: Python<'py>, actions: Vec<usize>)
+                 -> PyResult<(Vec<Bound<'py, PyDict>>, Vec<i32>, Vec<bool>, Vec<Bound<'py, PyDict>>)> {
+        if actions.len() != self.envs.len() {
+            return Err(PyValueError::new_err("Need one action per environment"));
+        }
+        let mut results = (Vec::new(), Vec::new(), Vec::new(), Vec::new());
+        for (env, action) in self.envs.iter_mut().zip(actions) {
+            let (mut observation, reward, done, info) = env.step(py, action)?;
+            if done {
+                info.set_item("final_observation", observation)?;
+                observation = env.reset(py, None)?;
+            }
+            results.0.push(observation);
+            results.1.push(reward);
+            results.2.push(done);
+            results.3.push(info);
+        }
+        Ok(results)
+    }
+
+    fn action_masks(&self) -> Vec<Vec<bool>> {
+        self.envs.iter().map(Env::action_mask).collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.envs.len()
+    }
+}
+
+#[pymodule]
+fn word_quest(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Env>()?;
+    m.add_class::<VecEnv>()?;
+    m.add("DISCARD", DISCARD)?;
+    m.add("SHUFFLE", SHUFFLE)?;
+    m.add("FIRST_WORD", FIRST_WORD)?;
+    Ok(())
+}
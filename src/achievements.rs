@@ -0,0 +1,159 @@
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+
+use crate::events::{self, GameEvent};
+use crate::i18n::{self, tr_args};
+use crate::mode::GameMode;
+use crate::ui::toast;
+use crate::unlocks::{Loadout, Modifier, Progress, Stake, TileSkin, Unlock};
+
+/// Names and descriptions live in the language files, keyed by `id`.
+pub struct Achievement {
+    pub id: &'static str,
+    pub reward: Option<Unlock>,
+    earned_by: fn(&GameEvent) -> bool,
+}
+
+impl Achievement {
+    pub fn name(&self) -> String {
+        i18n::tr(&format!("achievement.{}.name", self.id)).to_string()
+    }
+
+    pub fn description(&self) -> String {
+        let rare_letters = i18n::current().rare_letters();
+        tr_args(&format!("achievement.{}.description", self.id), &[("letters", &rare_letters)])
+    }
+}
+
+pub static ACHIEVEMENTS: [Achievement; 8] = [
+    Achievement {
+        id: "eight_letter_word",
+        reward: Some(Unlock::Modifier(Modifier::LongWords)),
+        earned_by: |event| matches!(event, GameEvent::WordScored { word, .. } if word.chars().count() >= 8),
+    },
+    Achievement {
+        id: "thousand_point_word",
+        reward: Some(Unlock::Skin(TileSkin::Gold)),
+        earned_by: |event| matches!(event, GameEvent::WordScored { score, .. } if *score >= 1000),
+    },
+    Achievement {
+        id: "rare_letter_word",
+        reward: Some(Unlock::Modifier(Modifier::RareBonus)),
+        earned_by: |event| matches!(event, GameEvent::WordScored { rare: true, .. }),
+    },
+    Achievement {
+        id: "one_word_round",
+        reward: Some(Unlock::Skin(TileSkin::Neon)),
+        earned_by: |event| matches!(event, GameEvent::RoundWon { words_used: 1, .. }),
+    },
+    Achievement {
+        id: "first_win",
+        reward: Some(Unlock::Stake(Stake::Hard)),
+        earned_by: |event| matches!(event, GameEvent::RunWon { .. }),
+    },
+    Achievement {
+        id: "no_discard_win",
+        reward: Some(Unlock::Modifier(Modifier::ExtraDiscard)),
+        earned_by: |event| matches!(event, GameEvent::RunWon { discards_used: 0, .. }),
+    },
+    Achievement {
+        id: "blitz_win",
+        reward: Some(Unlock::Skin(TileSkin::Mint)),
+        earned_by: |event| matches!(event, GameEvent::RunWon { mode: GameMode::Blitz, .. }),
+    },
+    Achievement {
+        id: "endless_round_ten",
+        reward: Some(Unlock::Stake(Stake::Brutal)),
+        earned_by: |event| matches!(event, GameEvent::RoundWon { mode: GameMode::Endless, round, .. }
+            if *round >= 10),
+    },
+];
+
+struct AchievementTracker {
+    progress: Progress,
+    /// Off while a cheated run is played.
+    counting: bool,
+}
+
+impl AchievementTracker {
+    fn open() -> AchievementTracker {
+        AchievementTracker { progress: Progress::load(), counting: true }
+    }
+
+    fn save(&self) {
+        if let Err(e) = self.progress.save() {
+            tracing::error!("{}", e);
+        }
+    }
+
+    fn handle(&mut self, event: &GameEvent) {
+        if !self.counting {
+            return;
+        }
+        let mut changed = false;
+        for achievement in ACHIEVEMENTS.iter() {
+            if !self.progress.earned.contains(achievement.id) && (achievement.earned_by)(event) {
+                self.progress.earned.insert(achievement.id.to_string());
+                toast::show(tr_args("achievements.unlocked", &[("name", &achievement.name())]));
+                if let Some(reward) = achievement.reward {
+                    toast::show(tr_args("achievements.new", &[("name", &reward.name())]));
+                }
+                changed = true;
+            }
+        }
+        if changed {
+            self.save();
+        }
+    }
+
+    fn is_unlocked(&self, unlock: Unlock) -> bool {
+        let is_reward = |a: &Achievement| a.reward == Some(unlock);
+        match ACHIEVEMENTS.iter().find(|a| is_reward(a)) {
+            Some(achievement) => self.progress.earned.contains(achievement.id),
+            None => true,
+        }
+    }
+}
+
+static TRACKER: Lazy<Mutex<AchievementTracker>> =
+    Lazy::new(|| Mutex::new(AchievementTracker::open()));
+
+/// Hooks the tracker up to the game's event bus.
+pub fn init() {
+    events::subscribe(|event| TRACKER.lock().unwrap().handle(event));
+}
+
+/// Whether what happens in the run being played counts toward achievements.
+pub fn count_run(counting: bool) {
+    TRACKER.lock().unwrap().counting = counting;
+}
+
+pub fn is_earned(achievement: &Achievement) -> bool {
+    TRACKER.lock().unwrap().progress.earned.contains(achievement.id)
+}
+
+/// Content that is not the reward of any achievement is always unlocked.
+pub fn is_unlocked(unlock: Unlock) -> bool {
+    TRACKER.lock().unwrap().is_unlocked(unlock)
+}
+
+pub fn loadout() -> Loadout {
+    let tracker = TRACKER.lock().unwrap();
+    let mut loadout = tracker.progress.loadout.clone();
+    // Never hand out content that was locked again, e.g. by a reset progress file.
+    if !tracker.is_unlocked(Unlock::Skin(loadout.skin)) {
+        loadout.skin = TileSkin::Classic;
+    }
+    if !tracker.is_unlocked(Unlock::Stake(loadout.stake)) {
+        loadout.stake = Stake::Normal;
+    }
+    loadout.modifiers.retain(|m| tracker.is_unlocked(Unlock::Modifier(*m)));
+    loadout
+}
+
+pub fn set_loadout(loadout: Loadout) {
+    let mut tracker = TRACKER.lock().unwrap();
+    tracker.progress.loadout = loadout;
+    tracker.save();
+}
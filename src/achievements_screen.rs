@@ -0,0 +1,168 @@
+use macroquad::prelude::*;
+
+use crate::achievements::{self, ACHIEVEMENTS};
+use crate::i18n::{tr, tr_args};
+use crate::input;
+use crate::scene::{Context, Scene, Transition};
+use crate::ui::button::Button;
+use crate::ui::layout;
+use crate::ui::theme;
+use crate::ui::widget::{self, Widget};
+use crate::unlocks::{Modifier, Stake, TileSkin, Unlock};
+
+pub struct AchievementsScreen {
+    skin_button: Button,
+    stake_button: Button,
+    modifier_buttons: Vec<Button>,
+    back_button: Button,
+}
+
+impl AchievementsScreen {
+    pub fn new() -> AchievementsScreen {
+        let theme = theme::current();
+        let x = layout::width() - 440.0;
+        let y = 100.0;
+        let mut screen = AchievementsScreen {
+            skin_button: Button::new(x, y + 50.0, 400.0, 50.0, theme.panel, String::new(), 30.0),
+            stake_button: Button::new(x, y + 115.0, 400.0, 50.0, theme.button, String::new(), 30.0),
+            modifier_buttons: (0..Modifier::ALL.len())
+                .map(|i| Button::new(x, y + 225.0 + i as f32 * 85.0, 400.0, 50.0, theme.panel, String::new(), 30.0))
+                .collect(),
+            back_button: Button::new(
+                layout::width() / 2.0 - 100.0,
+                layout::height() - 70.0,
+                200.0,
+                50.0,
+                theme.button,
+                tr("common.back").to_string(),
+                40.0
+            ),
+        };
+        screen.refresh();
+        screen
+    }
+
+    /// Updates the loadout buttons to show the saved loadout.
+    fn refresh(&mut self) {
+        let loadout = achievements::loadout();
+        let theme = theme::current();
+        self.skin_button.set_color(theme::skin_color(loadout.skin));
+        self.skin_button.set_text(&tr_args("achievements.tiles", &[("skin", &loadout.skin.name())]));
+        self.stake_button.set_text(&tr_args("achievements.stake", &[("stake", &loadout.stake.name())]));
+
+        for (modifier, button) in Modifier::ALL.into_iter().zip(&mut self.modifier_buttons) {
+            let unlocked = achievements::is_unlocked(Unlock::Modifier(modifier));
+            let active = loadout.modifiers.contains(&modifier);
+            let color = match (unlocked, active) {
+                (false, _) => theme.disabled,
+                (true, true) => theme.accent,
+                (true, false) => theme.panel,
+            };
+            button.set_color(color);
+            button.set_text(if unlocked { modifier.name() } else { tr("achievements.locked") });
+        }
+    }
+}
+
+impl Scene for AchievementsScreen {
+    fn handle_input(&mut self, _ctx: &mut Context) -> Transition {
+        let mut focusable: Vec<&mut dyn Widget> = vec![&mut self.skin_button, &mut self.stake_button];
+        for button in self.modifier_buttons.iter_mut() {
+            focusable.push(button);
+        }
+        focusable.push(&mut self.back_button);
+        widget::focus(&mut focusable);
+
+        let mut loadout = achievements::loadout();
+        let mut changed = false;
+        if self.skin_button.update() {
+            loadout.skin = next_unlocked(&TileSkin::ALL, loadout.skin, Unlock::Skin);
+            changed = true;
+        }
+        if self.stake_button.update() {
+            loadout.stake = next_unlocked(&Stake::ALL, loadout.stake, Unlock::Stake);
+            changed = true;
+        }
+        for (modifier, button) in Modifier::ALL.into_iter().zip(&mut self.modifier_buttons) {
+            let unlocked = achievements::is_unlocked(Unlock::Modifier(modifier));
+            if button.update() && unlocked {
+                if loadout.modifiers.contains(&modifier) {
+                    loadout.modifiers.retain(|m| *m != modifier);
+                } else {
+                    loadout.modifiers.push(modifier);
+                }
+                changed = true;
+            }
+        }
+        if changed {
+            achievements::set_loadout(loadout);
+            self.refresh();
+        }
+
+        if self.back_button.update() || input::is_back_pressed() {
+            return Transition::Pop;
+        }
+        Transition::None
+    }
+
+    fn draw(&self, _ctx: &Context) {
+        let theme = theme::current();
+        layout::clear(theme.background);
+
+        let title = tr("achievements.title");
+        let title_dim = theme::measure_text(title, 60);
+        theme::draw_text(title, layout::width() / 2.0 - title_dim.width / 2.0, 70.0, 60.0, theme.text);
+
+        draw_gallery(40.0, 100.0);
+
+        let x = layout::width() - 440.0;
+        theme::draw_text(tr("achievements.loadout"), x, 130.0, 40.0, theme.text);
+        theme::draw_text(tr("achievements.modifiers"), x, 310.0, 30.0, theme.text);
+        for (modifier, button) in Modifier::ALL.into_iter().zip(&self.modifier_buttons) {
+            if achievements::is_unlocked(Unlock::Modifier(modifier)) {
+                let rect = button.rect();
+                theme::draw_text(&modifier.description(), rect.x, rect.y + 70.0, 20.0, theme.text);
+            }
+        }
+
+        widget::draw_all(&[&self.skin_button, &self.stake_button]);
+        for button in &self.modifier_buttons {
+            button.draw();
+        }
+        self.back_button.draw();
+    }
+}
+
+fn draw_gallery(x: f32, y: f32) {
+    let theme = theme::current();
+    let width = 740.0;
+    let row_height = 66.0;
+    theme::draw_rounded_rect(x, y, width, row_height * ACHIEVEMENTS.len() as f32 + 10.0,
+                             theme.corner_radius, theme.panel);
+
+    let mut y_pos = y + 10.0;
+    for achievement in ACHIEVEMENTS.iter() {
+        let earned = achievements::is_earned(achievement);
+        let (badge, color) = if earned { ("*", theme.success) } else { ("-", theme.disabled) };
+
+        theme::draw_text(badge, x + 15.0, y_pos + 30.0, 40.0, color);
+        theme::draw_text(&achievement.name(), x + 45.0, y_pos + 22.0, 30.0, color);
+        theme::draw_text(&achievement.description(), x + 45.0, y_pos + 48.0, 22.0, theme.panel_text);
+        if let Some(reward) = achievement.reward {
+            let reward_text = tr_args("achievements.unlocks", &[("reward", &reward.name())]);
+            let reward_dim = theme::measure_text(&reward_text, 22);
+            theme::draw_text(&reward_text, x + width - reward_dim.width - 15.0, y_pos + 22.0, 22.0, theme.highlight);
+        }
+        y_pos += row_height;
+    }
+}
+
+/// The next option after `current` that the player has unlocked, wrapping
+/// around to the first option.
+fn next_unlocked<T: Copy + PartialEq>(options: &[T], current: T, unlock: fn(T) -> Unlock) -> T {
+    let start = options.iter().position(|o| *o == current).unwrap_or(0);
+    (1..=options.len())
+        .map(|offset| options[(start + offset) % options.len()])
+        .find(|option| achievements::is_unlocked(unlock(*option)))
+        .unwrap_or(current)
+}
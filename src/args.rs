@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::rules::Ruleset;
+
+/// The command line split into positional arguments and `--name value`
+/// options.
+pub struct Args {
+    pub positional: Vec<String>,
+    options: HashMap<String, String>,
+}
+
+impl Args {
+    /// `flags` are the options that never take a value and `valued` the ones
+    /// that always do. Any other option is an error. `-h` is short for
+    /// `--help`.
+    pub fn parse(args: impl IntoIterator<Item = String>, flags: &[&str], valued: &[&str]) -> Result<Args, String> {
+        let mut positional = Vec::new();
+        let mut options = HashMap::new();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if arg == "-h" {
+                options.insert("help".to_string(), String::new());
+            } else if let Some(name) = arg.strip_prefix("--") {
+                let value = if flags.contains(&name) {
+                    String::new()
+                } else if valued.contains(&name) {
+                    args.next().ok_or_else(|| format!("--{} needs a value", name))?
+                } else {
+                    return Err(format!("Unknown option: {}", arg));
+                };
+                options.insert(name.to_string(), value);
+            } else {
+                positional.push(arg);
+            }
+        }
+        Ok(Args { positional, options })
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        self.options.contains_key(name)
+    }
+
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(String::as_str)
+    }
+
+    /// The option parsed as a `T`, or `default` when it wasn't given.
+    pub fn parse_or<T: FromStr>(&self, name: &str, default: T) -> Result<T, String> {
+        match self.value(name) {
+            Some(value) => value.parse().map_err(|_| format!("Invalid value for --{}: {}", name, value)),
+            None => Ok(default),
+        }
+    }
+
+    /// The ruleset file given with `--rules`, or the game's own rules.
+    pub fn ruleset(&self) -> Result<Ruleset, String> {
+        match self.value("rules") {
+            Some(path) => Ruleset::load(Path::new(path)),
+            None => Ok(Ruleset::default()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> Result<Args, String> {
+        Args::parse(line.split_whitespace().map(str::to_string), &["json", "help"], &["runs", "seed"])
+    }
+
+    #[test]
+    fn options_are_split_from_positional_arguments() {
+        let args = parse("simulate --runs 5 --json extra").unwrap();
+        assert_eq!(args.positional, ["simulate", "extra"]);
+        assert_eq!(args.value("runs"), Some("5"));
+        assert!(args.flag("json"));
+        assert_eq!(args.parse_or("seed", 1), Ok(1));
+        assert!(parse("-h").unwrap().flag("help"));
+    }
+
+    #[test]
+    fn unknown_options_are_errors() {
+        assert_eq!(parse("simulate --runz 5").err(), Some("Unknown option: --runz".to_string()));
+    }
+
+    #[test]
+    fn valued_options_need_their_value() {
+        assert_eq!(parse("simulate --runs").err(), Some("--runs needs a value".to_string()));
+        assert!(parse("--runs many").unwrap().parse_or("runs", 0).is_err());
+    }
+}
@@ -0,0 +1,252 @@
+use macroquad::prelude::*;
+
+use crate::events::{self, GameEvent};
+use crate::settings;
+
+/// How long one music track takes to fade into the next.
+const CROSSFADE_SECONDS: f32 = 1.5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Sound {
+    TileClick,
+    ValidWord,
+    InvalidWord,
+    ScoreTick,
+    RoundWin,
+    GameOver,
+    Shuffle,
+    Discard,
+}
+
+/// Looping background tracks. Each scene picks one and the player crossfades
+/// between them as scenes change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Music {
+    Menu,
+    Game,
+}
+
+/// Starts loading the sound files in the background and plays effects for
+/// game events. Files that are missing are skipped, so the game still runs
+/// without them, just more quietly.
+pub fn init() {
+    backend::start_loading();
+    events::subscribe(|event| match event {
+        GameEvent::WordScored { .. } => play(Sound::ValidWord),
+        GameEvent::WordRejected { .. } => play(Sound::InvalidWord),
+        GameEvent::Shuffled => play(Sound::Shuffle),
+        GameEvent::Discarded => play(Sound::Discard),
+        GameEvent::RoundWon { .. } | GameEvent::RunWon { .. } => play(Sound::RoundWin),
+        GameEvent::RunLost { .. } => play(Sound::GameOver),
+    });
+}
+
+pub fn play(sound: Sound) {
+    let settings = settings::get();
+    backend::play(sound, settings.master_volume * settings.effects_volume);
+}
+
+/// Switches the background music to `track`, fading out whatever played
+/// before. Asking for the track that's already playing does nothing.
+pub fn play_music(track: Music) {
+    backend::play_music(track);
+}
+
+/// How many sound files have been tried, loaded or not, out of how many
+/// will be. Both are 0 when nothing is loading, e.g. without the `audio`
+/// feature or with `--no-audio`.
+pub fn loading_progress() -> (usize, usize) {
+    backend::loading_progress()
+}
+
+/// Advances crossfades and applies volume changes from the settings. Called
+/// once per frame.
+pub fn update() {
+    let volume = {
+        let settings = settings::get();
+        settings.master_volume * settings.music_volume
+    };
+    backend::update(get_frame_time() / CROSSFADE_SECONDS, volume);
+}
+
+#[cfg(feature = "audio")]
+mod backend {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    use macroquad::audio::{self, PlaySoundParams};
+    use macroquad::experimental::coroutines::start_coroutine;
+
+    use super::{Music, Sound};
+
+    /// Where sound files are looked up, relative to the working directory.
+    const AUDIO_DIR: &str = "assets/audio";
+
+    const SOUNDS: [Sound; 8] = [
+        Sound::TileClick,
+        Sound::ValidWord,
+        Sound::InvalidWord,
+        Sound::ScoreTick,
+        Sound::RoundWin,
+        Sound::GameOver,
+        Sound::Shuffle,
+        Sound::Discard,
+    ];
+
+    const TRACKS: [Music; 2] = [Music::Menu, Music::Game];
+
+    fn sound_file(sound: Sound) -> &'static str {
+        match sound {
+            Sound::TileClick => "tile_click.wav",
+            Sound::ValidWord => "valid_word.wav",
+            Sound::InvalidWord => "invalid_word.wav",
+            Sound::ScoreTick => "score_tick.wav",
+            Sound::RoundWin => "round_win.wav",
+            Sound::GameOver => "game_over.wav",
+            Sound::Shuffle => "shuffle.wav",
+            Sound::Discard => "discard.wav",
+        }
+    }
+
+    fn music_file(track: Music) -> &'static str {
+        match track {
+            Music::Menu => "menu.ogg",
+            Music::Game => "game.ogg",
+        }
+    }
+
+    /// A music track that is fading in, playing, or fading out.
+    struct Channel {
+        track: Music,
+        /// From 0 (silent) to 1 (full music volume).
+        fade: f32,
+        started: bool,
+    }
+
+    #[derive(Default)]
+    struct Backend {
+        sounds: HashMap<Sound, audio::Sound>,
+        music: HashMap<Music, audio::Sound>,
+        wanted: Option<Music>,
+        channels: Vec<Channel>,
+        files_tried: usize,
+        files_total: usize,
+    }
+
+    thread_local! {
+        static BACKEND: RefCell<Backend> = RefCell::new(Backend::default());
+    }
+
+    async fn load(file: &str) -> Option<audio::Sound> {
+        let path = format!("{}/{}", AUDIO_DIR, file);
+        match audio::load_sound(&path).await {
+            Ok(sound) => Some(sound),
+            Err(e) => {
+                tracing::warn!(path, "Failed to load sound, playing without it: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Loads every file on a coroutine, so the first frames don't wait on
+    /// the disk. Sounds play as soon as they're loaded.
+    pub fn start_loading() {
+        BACKEND.with(|backend| backend.borrow_mut().files_total = SOUNDS.len() + TRACKS.len());
+        start_coroutine(async {
+            for sound in SOUNDS {
+                let clip = load(sound_file(sound)).await;
+                BACKEND.with(|backend| {
+                    let backend = &mut *backend.borrow_mut();
+                    backend.files_tried += 1;
+                    if let Some(clip) = clip {
+                        backend.sounds.insert(sound, clip);
+                    }
+                });
+            }
+            for track in TRACKS {
+                let clip = load(music_file(track)).await;
+                BACKEND.with(|backend| {
+                    let backend = &mut *backend.borrow_mut();
+                    backend.files_tried += 1;
+                    if let Some(clip) = clip {
+                        backend.music.insert(track, clip);
+                    }
+                });
+            }
+        });
+    }
+
+    pub fn loading_progress() -> (usize, usize) {
+        BACKEND.with(|backend| {
+            let backend = backend.borrow();
+            (backend.files_tried, backend.files_total)
+        })
+    }
+
+    pub fn play(sound: Sound, volume: f32) {
+        BACKEND.with(|backend| {
+            if let Some(clip) = backend.borrow().sounds.get(&sound) {
+                audio::play_sound(clip, PlaySoundParams { looped: false, volume });
+            }
+        });
+    }
+
+    pub fn play_music(track: Music) {
+        BACKEND.with(|backend| {
+            let backend = &mut *backend.borrow_mut();
+            backend.wanted = Some(track);
+            if !backend.channels.iter().any(|channel| channel.track == track) {
+                backend.channels.push(Channel { track, fade: 0.0, started: false });
+            }
+        });
+    }
+
+    /// Moves every channel `step` closer to full volume if it's the wanted
+    /// track, or to silence otherwise, and stops channels that went silent.
+    pub fn update(step: f32, volume: f32) {
+        BACKEND.with(|backend| {
+            let backend = &mut *backend.borrow_mut();
+            let wanted = backend.wanted;
+            let music = &backend.music;
+            backend.channels.retain_mut(|channel| {
+                let fading_in = wanted == Some(channel.track);
+                channel.fade = if fading_in {
+                    (channel.fade + step).min(1.0)
+                } else {
+                    (channel.fade - step).max(0.0)
+                };
+                // Tracks still loading start as soon as they're ready.
+                let Some(clip) = music.get(&channel.track) else {
+                    return fading_in;
+                };
+                if !fading_in && channel.fade == 0.0 {
+                    audio::stop_sound(clip);
+                    return false;
+                }
+                if !channel.started {
+                    audio::play_sound(clip, PlaySoundParams { looped: true, volume: 0.0 });
+                    channel.started = true;
+                }
+                audio::set_sound_volume(clip, channel.fade * volume);
+                true
+            });
+        });
+    }
+}
+
+#[cfg(not(feature = "audio"))]
+mod backend {
+    use super::{Music, Sound};
+
+    pub fn start_loading() {}
+
+    pub fn loading_progress() -> (usize, usize) {
+        (0, 0)
+    }
+
+    pub fn play(_sound: Sound, _volume: f32) {}
+
+    pub fn play_music(_track: Music) {}
+
+    pub fn update(_step: f32, _volume: f32) {}
+}
@@ -0,0 +1,250 @@
+//! Lets players written in other languages play runs over newline-delimited
+//! JSON, through stdin/stdout or a socket on this machine.
+//!
+//! The game sends an `observation` before every move and an `end` message
+//! once the run is over. The bot answers each observation with a move, e.g.
+//! `{"action": "play", "word": "quiz"}`, `{"action": "discard"}` or
+//! `{"action": "shuffle"}`.
+
+use std::collections::HashSet;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::process::{Child, Command, Stdio};
+use std::sync::Arc;
+use std::thread;
+
+use ci210_word_game::args::Args;
+use ci210_word_game::i18n::Language;
+use ci210_word_game::mode::GameMode;
+use ci210_word_game::rules::{self, Move, Outcome, Run};
+use ci210_word_game::unlocks::Loadout;
+use serde_json::{json, Value};
+
+use crate::simulate::{RunResult, MAX_MOVES};
+
+fn send(output: &mut impl Write, message: Value) -> io::Result<()> {
+    writeln!(output, "{}", message)?;
+    output.flush()
+}
+
+fn result_json(result: &RunResult) -> Value {
+    json!({
+        "seed": result.seed,
+        "won": result.won,
+        "rounds_won": result.rounds_won,
+        "run_score": result.run_score,
+        "miss": result.miss,
+    })
+}
+
+fn observation(run: &Run, last: &Value) -> Value {
+    let mut message = serde_json::to_value(run.observe()).unwrap_or_default();
+    message["type"] = json!("observation");
+    message["last"] = last.clone();
+    message
+}
+
+/// Plays `run` with the bot on the other end of `input` and `output`. A bot
+/// that disconnects, or can't be written to any more, loses its run there.
+/// One that keeps going past the move limit has its run stopped.
+fn serve(mut run: Run, input: impl BufRead, mut output: impl Write) -> RunResult {
+    let mut lines = input.lines();
+    let mut last = Value::Null;
+    let mut outcome = Outcome::Playing;
+    run.deal();
+    for _ in 0..MAX_MOVES {
+        let line = match send(&mut output, observation(&run, &last)) {
+            Ok(()) => lines.next().and_then(Result::ok),
+            Err(_) => None,
+        };
+        let Some(line) = line else {
+            outcome = Outcome::RunLost;
+            break;
+        };
+        last = match serde_json::from_str::<Move>(&line) {
+            Ok(action) => {
+                let score = run.make_move(&action);
+                json!({ "move": action, "score": score })
+            }
+            Err(e) => json!({ "error": e.to_string() }),
+        };
+        outcome = run.check();
+        if matches!(outcome, Outcome::RunWon | Outcome::RunLost) {
+            break;
+        }
+        run.deal();
+    }
+
+    let result = RunResult::new(&run, outcome);
+    let mut end = result_json(&result);
+    end["type"] = json!("end");
+    // The bot may already be gone, which changes nothing about the result.
+    let _ = send(&mut output, end);
+    result
+}
+
+fn new_run(language: &'static Language, words_db: &Arc<HashSet<String>>, args: &Args,
+           seed: Option<u64>) -> Result<Run, String> {
+    let mode: GameMode = args.parse_or("mode", GameMode::Standard)?;
+    let mut run = Run::new(mode, language, words_db.clone(), args.ruleset()?, Loadout::default(), false);
+    if let Some(seed) = seed {
+        run.set_seed(seed);
+    }
+    Ok(run)
+}
+
+fn seed(args: &Args) -> Result<Option<u64>, String> {
+    args.value("seed").map(|_| args.parse_or("seed", 0)).transpose()
+}
+
+/// Plays one run over stdin/stdout, or with `--listen <port>` one run per
+/// connection to that port on 127.0.0.1. Seeds count up from `--seed`.
+pub fn bot(language: &'static Language, args: &Args) -> Result<i32, String> {
+    let words_db = Arc::new(rules::initialize_words_db(language)?);
+    let mut seed = seed(args)?;
+
+    let Some(port) = args.value("listen") else {
+        let run = new_run(language, &words_db, args, seed)?;
+        serve(run, io::stdin().lock(), io::stdout().lock());
+        return Ok(0);
+    };
+
+    let port: u16 = port.parse().map_err(|_| format!("Invalid port: {}", port))?;
+    let listener = TcpListener::bind(("127.0.0.1", port)).map_err(|e| format!("Failed to listen: {}", e))?;
+    println!("Listening on 127.0.0.1:{}", port);
+    for stream in listener.incoming() {
+        let stream = stream.map_err(|e| e.to_string())?;
+        let reader = BufReader::new(stream.try_clone().map_err(|e| e.to_string())?);
+        let run = new_run(language, &words_db, args, seed)?;
+        println!("{}", result_json(&serve(run, reader, stream)));
+        seed = seed.map(|seed| seed.wrapping_add(1));
+    }
+    Ok(0)
+}
+
+/// Starts a bot command through the shell, talking to it over its stdin/stdout.
+fn spawn(command: &str) -> Result<Child, String> {
+    let (shell, flag) = if cfg!(windows) { ("cmd", "/C") } else { ("sh", "-c") };
+    Command::new(shell)
+        .args([flag, command])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start {}: {}", command, e))
+}
+
+/// Bots are ranked by a won run, then the rounds they won, then their score.
+fn rank(result: &RunResult) -> (bool, u32, i32) {
+    (result.won, result.rounds_won, result.run_score)
+}
+
+/// Plays `run` with a started bot, then stops it.
+fn play_bot(bot: &mut Child, run: Run) -> RunResult {
+    let input = BufReader::new(bot.stdout.take().expect("Bot stdout is piped"));
+    let output = bot.stdin.take().expect("Bot stdin is piped");
+    let result = serve(run, input, output);
+    let _ = bot.kill();
+    let _ = bot.wait();
+    result
+}
+
+/// Plays two bots on the same seed at the same time and reports the winner.
+pub fn referee(language: &'static Language, first: &str, second: &str, args: &Args) -> Result<i32, String> {
+    let words_db = Arc::new(rules::initialize_words_db(language)?);
+    let first_run = new_run(language, &words_db, args, seed(args)?)?;
+    let second_run = new_run(language, &words_db, args, Some(first_run.seed))?;
+
+    let mut first_bot = spawn(first)?;
+    let mut second_bot = spawn(second)?;
+    let (first_result, second_result) = thread::scope(|scope| {
+        let first_game = scope.spawn(|| play_bot(&mut first_bot, first_run));
+        let second_result = play_bot(&mut second_bot, second_run);
+        (first_game.join().unwrap(), second_result)
+    });
+
+    let winner = match rank(&first_result).cmp(&rank(&second_result)) {
+        std::cmp::Ordering::Greater => Some(first),
+        std::cmp::Ordering::Less => Some(second),
+        std::cmp::Ordering::Equal => None,
+    };
+    if args.flag("json") {
+        println!("{}", serde_json::to_string_pretty(&json!({
+            "seed": first_result.seed,
+            "bots": [
+                { "command": first, "result": result_json(&first_result) },
+                { "command": second, "result": result_json(&second_result) },
+            ],
+            "winner": winner,
+        })).unwrap_or_default());
+    } else {
+        for (command, result) in [(first, &first_result), (second, &second_result)] {
+            let outcome = if result.won { "won" } else { "lost" };
+            println!("{}: {} after {} rounds with {} points", command, outcome, result.rounds_won, result.run_score);
+        }
+        match winner {
+            Some(winner) => println!("Winner: {}", winner),
+            None => println!("Draw"),
+        }
+    }
+    Ok(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ci210_word_game::mode::GameMode;
+    use ci210_word_game::rules::Ruleset;
+    use ci210_word_game::{i18n, json};
+
+    fn run(seed: u64) -> Run {
+        let language = i18n::language("Español").unwrap();
+        let words = json::get_available_words(&language.dictionary).unwrap();
+        let mut run = Run::new(GameMode::Standard, language, Arc::new(words.into_iter().collect()),
+                               Ruleset::default(), Loadout::default(), false);
+        run.set_seed(seed);
+        run
+    }
+
+    /// Plays a run of `seed` with a bot that sends `input` and then hangs
+    /// up. Returns the result and every message the bot was sent.
+    fn play(seed: u64, input: &str) -> (RunResult, Vec<Value>) {
+        let mut output = Vec::new();
+        let result = serve(run(seed), input.as_bytes(), &mut output);
+        let messages = String::from_utf8(output).unwrap().lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        (result, messages)
+    }
+
+    #[test]
+    fn moves_and_their_outcomes_go_both_ways() {
+        let input = "{\"action\": \"shuffle\"}\nnot json\n{\"action\": \"discard\"}\n";
+        let (result, messages) = play(9, input);
+        assert_eq!(messages.len(), 5);
+        assert!(messages[..4].iter().all(|message| message["type"] == "observation"));
+        assert!(messages[0]["last"].is_null());
+        assert_eq!(messages[0]["seed"], 9);
+        assert_eq!(messages[1]["last"]["move"], json!({ "action": "shuffle" }));
+        assert!(messages[2]["last"]["error"].is_string());
+        assert_eq!(messages[3]["last"]["move"], json!({ "action": "discard" }));
+        assert_eq!(messages[3]["discards"], messages[0]["discards"].as_u64().unwrap() - 1);
+
+        // Hanging up loses the run.
+        assert_eq!(messages[4]["type"], "end");
+        assert_eq!(messages[4]["won"], false);
+        assert!(!result.won);
+        assert!(result.miss.is_some());
+        assert_eq!(play(9, input).1, messages);
+    }
+
+    #[test]
+    fn a_played_word_is_scored() {
+        let mut dealt = run(3);
+        dealt.deal();
+        let word = dealt.best_word().unwrap().clone();
+        let action = json!({ "action": "play", "word": word });
+        let (_, messages) = play(3, &format!("{}\n", action));
+        assert_eq!(messages[1]["last"]["move"], action);
+        assert_eq!(messages[1]["last"]["score"], dealt.word_score(&word));
+    }
+}
@@ -0,0 +1,93 @@
+//! Command-line tools for Word Quest: scoring, checking and solving words
+//! with the same dictionaries and letter tables as the game.
+
+mod bot;
+mod simulate;
+mod words;
+
+use std::process;
+
+use ci210_word_game::args::Args;
+use ci210_word_game::i18n::{self, Language};
+
+const USAGE: &str = "\
+Usage: word-quest <command> [options]
+
+Commands:
+  score <word>               Score a word
+  check <word>               Check whether a word can be played
+  solve <letters> [--top N]  List the best words that can be built from the letters
+  dict stats                 Summarize the dictionary
+  simulate                   Play many runs with a scripted player and report how they went
+  bot, --bot                 Play a run with a bot over newline-delimited JSON on stdin/stdout
+  referee <bot> <bot>        Play two bot commands on the same seed and report the winner
+
+Simulate options:
+  --runs <n>                 How many runs to play (default: 1000)
+  --strategy <name>          greedy, random or discard-when-weak (default: greedy)
+  --mode <name>              standard, endless, daily or blitz (default: standard)
+  --seed <n>                 The first run's seed. Each run after it adds one (default: 1)
+  --rules <file>             A JSON ruleset to play by instead of the game's own
+  --threads <n>              How many runs to play at once (default: one per core)
+  --format <name>            text, json, or csv with one line per run (default: text)
+
+Bot options:
+  --listen <port>            Play one run per connection to 127.0.0.1:<port> instead of stdin/stdout
+  --mode, --seed, --rules    As for simulate. Without --seed, bots play a random seed
+
+Options:
+  --language <name>          English, Español or Deutsch (default: English)
+  --json                     Print JSON instead of text
+  -h, --help                 Print this help";
+
+/// Runs the command and returns the exit code.
+fn run(args: &Args) -> Result<i32, String> {
+    let language = language(args.value("language"))?;
+    let json = args.flag("json");
+    let positional: Vec<&str> = args.positional.iter().map(String::as_str).collect();
+    if args.flag("bot") {
+        return match positional.as_slice() {
+            [] | ["bot"] => bot::bot(language, args),
+            _ => Err(format!("--bot takes no command: {}\n\n{}", positional.join(" "), USAGE)),
+        };
+    }
+    match positional.as_slice() {
+        ["score", word] => words::score(language, word, json),
+        ["check", word] => words::check(language, word, json),
+        ["solve", letters] => words::solve(language, letters, args.parse_or("top", 10)?, json),
+        ["dict", "stats"] => words::stats(language, json),
+        ["simulate"] => simulate::simulate(language, args),
+        ["bot"] => bot::bot(language, args),
+        ["referee", first, second] => bot::referee(language, first, second, args),
+        [] => Err(format!("Missing command\n\n{}", USAGE)),
+        _ => Err(format!("Unknown command: {}\n\n{}", positional.join(" "), USAGE)),
+    }
+}
+
+/// Languages are matched by name, ignoring case.
+fn language(name: Option<&str>) -> Result<&'static Language, String> {
+    let name = name.unwrap_or(i18n::DEFAULT_LANGUAGE);
+    i18n::find(name).ok_or_else(|| format!("Unknown language: {}", name))
+}
+
+fn main() {
+    let valued = ["language", "top", "runs", "strategy", "mode", "seed", "rules", "threads", "format", "listen"];
+    let args = match Args::parse(std::env::args().skip(1), &["json", "bot", "help"], &valued) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Error: {}\n\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+    if args.flag("help") {
+        println!("{}", USAGE);
+        return;
+    }
+    match run(&args) {
+        Ok(code) => process::exit(code),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(2);
+        }
+    }
+}
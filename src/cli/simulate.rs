@@ -0,0 +1,303 @@
+//! Plays many seeded runs without a window, with a scripted player, to see
+//! how a ruleset plays out.
+
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::thread;
+
+use ci210_word_game::args::Args;
+use ci210_word_game::i18n::Language;
+use ci210_word_game::mode::GameMode;
+use ci210_word_game::rules::{self, Move, Outcome, Ruleset, Run};
+use ci210_word_game::unlocks::Loadout;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde_json::json;
+
+
+/// Endless runs that keep winning are stopped here.
+const MAX_ROUNDS: u32 = 50;
+
+/// A simulated run that neither wins nor loses within this many moves is
+/// stopped, e.g. when invalid words are free.
+pub const MAX_MOVES: u32 = 1000;
+
+/// How the scripted player picks its moves.
+#[derive(Clone, Copy, Debug)]
+pub enum Strategy {
+    /// Always plays the best word on the rack.
+    Greedy,
+    /// Plays any word the rack allows.
+    Random,
+    /// Plays the best word, unless it's too weak to reach the target with
+    /// the words left and a discard is available.
+    DiscardWhenWeak,
+}
+
+impl FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Strategy, String> {
+        match name {
+            "greedy" => Ok(Strategy::Greedy),
+            "random" => Ok(Strategy::Random),
+            "discard-when-weak" => Ok(Strategy::DiscardWhenWeak),
+            _ => Err(format!("Unknown strategy: {}", name)),
+        }
+    }
+}
+
+impl Strategy {
+    fn choose(&self, run: &Run, rng: &mut StdRng) -> Move {
+        let word = match self {
+            Strategy::Random => {
+                // Sorted first, as the dictionary's order changes from one
+                // process to the next and a seed should always play the same.
+                let mut words: Vec<&String> = run.playable_words().collect();
+                words.sort();
+                words.choose(rng).map(|word| word.to_string())
+            }
+            _ => run.best_word().cloned(),
+        };
+        let Some(word) = word else {
+            if run.discards > 0 {
+                return Move::Discard;
+            }
+            // Nothing fits and nothing can be thrown away, so a word is wasted.
+            return Move::Play { word: run.rack.iter().collect() };
+        };
+
+        if let Strategy::DiscardWhenWeak = self {
+            let needed = (run.round_score as i32 - run.total_score) / run.words_remaining.max(1) as i32;
+            if run.discards > 0 && run.word_score(&word) * 2 < needed {
+                return Move::Discard;
+            }
+        }
+        Move::Play { word }
+    }
+}
+
+/// How a run played without a screen went.
+pub struct RunResult {
+    pub seed: u64,
+    pub won: bool,
+    pub rounds_won: u32,
+    pub run_score: i32,
+    /// How far the last round's score fell short of its target, for lost runs.
+    pub miss: Option<i64>,
+}
+
+impl RunResult {
+    /// The result of a run that ended with `outcome`. Runs stopped before
+    /// they ended count as neither won nor lost.
+    pub fn new(run: &Run, outcome: Outcome) -> RunResult {
+        let won = outcome == Outcome::RunWon;
+        RunResult {
+            seed: run.seed,
+            won,
+            rounds_won: if won { run.round } else { run.round - 1 },
+            run_score: run.run_score,
+            miss: (outcome == Outcome::RunLost).then(|| run.round_score as i64 - run.total_score as i64),
+        }
+    }
+
+    /// Lost runs also played the round they lost.
+    fn rounds_played(&self) -> u32 {
+        self.rounds_won + self.miss.map_or(0, |_| 1)
+    }
+}
+
+fn simulate_run(strategy: Strategy, mode: GameMode, language: &'static Language,
+                words_db: Arc<HashSet<String>>, ruleset: &Ruleset, seed: u64) -> RunResult {
+    let mut run = Run::new(mode, language, words_db, ruleset.clone(), Loadout::default(), false);
+    run.set_seed(seed);
+    let mut rng = StdRng::seed_from_u64(seed.rotate_left(32));
+    run.deal();
+
+    let mut outcome = Outcome::Playing;
+    for _ in 0..MAX_MOVES {
+        run.make_move(&strategy.choose(&run, &mut rng));
+        outcome = run.check();
+        if matches!(outcome, Outcome::RunWon | Outcome::RunLost) || run.round > MAX_ROUNDS {
+            break;
+        }
+        run.deal();
+    }
+
+    RunResult::new(&run, outcome)
+}
+
+/// The smallest, largest, mean and a few percentiles of `values`.
+fn summary(mut values: Vec<i64>) -> serde_json::Value {
+    if values.is_empty() {
+        return serde_json::Value::Null;
+    }
+    values.sort_unstable();
+    let percentile = |p: usize| values[(values.len() - 1) * p / 100];
+    json!({
+        "min": values[0],
+        "p10": percentile(10),
+        "median": percentile(50),
+        "mean": values.iter().sum::<i64>() as f64 / values.len() as f64,
+        "p90": percentile(90),
+        "max": values[values.len() - 1],
+    })
+}
+
+fn report(results: &[RunResult], strategy: Strategy, mode: GameMode, ruleset: &Ruleset) -> serde_json::Value {
+    let last_round = results.iter().map(RunResult::rounds_played).max().unwrap_or(0);
+    let rounds: Vec<_> = (1..=last_round)
+        .map(|round| {
+            let played = results.iter().filter(|result| result.rounds_played() >= round).count();
+            let won = results.iter().filter(|result| result.rounds_won >= round).count();
+            json!({
+                "round": round,
+                "played": played,
+                "won": won,
+                "win_rate": won as f64 / played.max(1) as f64,
+            })
+        })
+        .collect();
+    let wins = results.iter().filter(|result| result.won).count();
+
+    json!({
+        "runs": results.len(),
+        "strategy": format!("{:?}", strategy),
+        "mode": format!("{:?}", mode),
+        "ruleset": ruleset,
+        "win_rate": wins as f64 / results.len().max(1) as f64,
+        "rounds": rounds,
+        "run_score": summary(results.iter().map(|result| result.run_score as i64).collect()),
+        "miss": summary(results.iter().filter_map(|result| result.miss).collect()),
+    })
+}
+
+fn print_text(report: &serde_json::Value) {
+    println!("{} runs, {} strategy, {} mode", report["runs"], report["strategy"].as_str().unwrap_or_default(),
+             report["mode"].as_str().unwrap_or_default());
+    println!("Win rate: {:.1}%", report["win_rate"].as_f64().unwrap_or(0.0) * 100.0);
+    println!();
+    println!("Round  Played     Won  Win rate");
+    for round in report["rounds"].as_array().into_iter().flatten() {
+        let count = |key: &str| round[key].as_u64().unwrap_or(0);
+        println!("{:>5}  {:>6}  {:>6}  {:>7.1}%", count("round"), count("played"), count("won"),
+                 round["win_rate"].as_f64().unwrap_or(0.0) * 100.0);
+    }
+    for (name, key) in [("Run score", "run_score"), ("Missed the target by", "miss")] {
+        let stats = &report[key];
+        if stats.is_null() {
+            continue;
+        }
+        println!();
+        println!("{}: min {}, p10 {}, median {}, mean {:.1}, p90 {}, max {}", name, stats["min"], stats["p10"],
+                 stats["median"], stats["mean"].as_f64().unwrap_or(0.0), stats["p90"], stats["max"]);
+    }
+}
+
+/// One line per run, for spreadsheets.
+fn print_csv(results: &[RunResult]) {
+    println!("seed,won,rounds_won,run_score,miss");
+    for result in results {
+        let miss = result.miss.map_or(String::new(), |miss| miss.to_string());
+        println!("{},{},{},{},{}", result.seed, result.won, result.rounds_won, result.run_score, miss);
+    }
+}
+
+pub fn simulate(language: &'static Language, args: &Args) -> Result<i32, String> {
+    let runs: u64 = args.parse_or("runs", 1000)?;
+    let strategy: Strategy = args.parse_or("strategy", Strategy::Greedy)?;
+    let mode: GameMode = args.parse_or("mode", GameMode::Standard)?;
+    let first_seed: u64 = args.parse_or("seed", 1)?;
+    let cores = thread::available_parallelism().map_or(1, |cores| cores.get());
+    let threads: u64 = args.parse_or("threads", cores as u64)?.max(1);
+    let ruleset = args.ruleset()?;
+    let format = if args.flag("json") { "json" } else { args.value("format").unwrap_or("text") };
+    if !["text", "json", "csv"].contains(&format) {
+        return Err(format!("Unknown format: {}", format));
+    }
+    let words_db = Arc::new(rules::initialize_words_db(language)?);
+
+    // Each thread plays every `threads`th seed, so the results don't
+    // depend on how many threads there are.
+    let mut results: Vec<RunResult> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|thread| {
+                let words_db = &words_db;
+                let ruleset = &ruleset;
+                scope.spawn(move || {
+                    (thread..runs).step_by(threads as usize)
+                        .map(|i| simulate_run(strategy, mode, language, words_db.clone(), ruleset,
+                                                  first_seed.wrapping_add(i)))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect()
+    });
+    results.sort_by_key(|result| result.seed);
+
+    match format {
+        "csv" => print_csv(&results),
+        "json" => println!("{}", serde_json::to_string_pretty(&report(&results, strategy, mode, &ruleset))
+            .unwrap_or_default()),
+        _ => print_text(&report(&results, strategy, mode, &ruleset)),
+    }
+    Ok(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ci210_word_game::{i18n, json};
+
+    /// The Spanish starter dictionary ships with the game, unlike the English one.
+    fn spanish() -> (&'static Language, Arc<HashSet<String>>) {
+        let language = i18n::language("Español").unwrap();
+        let words = json::get_available_words(&language.dictionary).unwrap();
+        (language, Arc::new(words.into_iter().collect()))
+    }
+
+    fn play(strategy: Strategy, seed: u64) -> (bool, u32, i32, Option<i64>) {
+        let (language, words_db) = spanish();
+        let result = simulate_run(strategy, GameMode::Standard, language, words_db, &Ruleset::default(), seed);
+        (result.won, result.rounds_won, result.run_score, result.miss)
+    }
+
+    #[test]
+    fn a_seed_always_plays_the_same_run() {
+        // Each run gets its own dictionary, which iterates in its own order.
+        for strategy in [Strategy::Greedy, Strategy::Random, Strategy::DiscardWhenWeak] {
+            for seed in 1..4 {
+                assert_eq!(play(strategy, seed), play(strategy, seed), "{:?} on seed {}", strategy, seed);
+            }
+        }
+    }
+
+    #[test]
+    fn strategies_parse_by_name() {
+        assert!(matches!("discard-when-weak".parse(), Ok(Strategy::DiscardWhenWeak)));
+        assert!("best".parse::<Strategy>().is_err());
+    }
+
+    #[test]
+    fn lost_runs_also_played_the_round_they_lost() {
+        let lost = RunResult { seed: 1, won: false, rounds_won: 2, run_score: 100, miss: Some(40) };
+        let stopped = RunResult { miss: None, ..lost };
+        assert_eq!(lost.rounds_played(), 3);
+        assert_eq!(stopped.rounds_played(), 2);
+    }
+
+    #[test]
+    fn summary_reports_percentiles() {
+        let stats = summary((1..=11).collect());
+        assert_eq!(stats["min"], 1);
+        assert_eq!(stats["p10"], 2);
+        assert_eq!(stats["median"], 6);
+        assert_eq!(stats["mean"], 6.0);
+        assert_eq!(stats["p90"], 10);
+        assert_eq!(stats["max"], 11);
+        assert!(summary(Vec::new()).is_null());
+    }
+}
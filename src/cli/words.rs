@@ -0,0 +1,153 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use ci210_word_game::i18n::{self, Language};
+use ci210_word_game::mode::GameMode;
+use ci210_word_game::rules::{self, Ruleset, Run};
+use ci210_word_game::unlocks::Loadout;
+use serde_json::json;
+
+/// A run with the language's dictionary loaded, for its scoring and
+/// checking rules. Nothing is dealt.
+fn open(language: &'static Language) -> Result<Run, String> {
+    let words_db = rules::initialize_words_db(language)?;
+    Ok(Run::new(GameMode::Standard, language, Arc::new(words_db), Ruleset::default(), Loadout::default(),
+                false))
+}
+
+/// The word in lower case, as the dictionaries have it. Letters the
+/// language doesn't deal are an error.
+fn normalize(language: &Language, word: &str) -> Result<String, String> {
+    let word = word.to_lowercase();
+    match word.chars().find(|&c| language.letter(c).is_none()) {
+        Some(c) => Err(format!("{} has no {} tile", language.name, i18n::display_letter(c))),
+        None => Ok(word),
+    }
+}
+
+fn display_word(word: &str) -> String {
+    word.chars().map(i18n::display_letter).collect()
+}
+
+fn print_json(value: serde_json::Value) {
+    println!("{}", serde_json::to_string_pretty(&value).unwrap_or_default());
+}
+
+pub fn score(language: &'static Language, word: &str, json: bool) -> Result<i32, String> {
+    let word = normalize(language, word)?;
+    let run = open(language)?;
+    let score = run.word_score(&word);
+    let valid = run.is_valid_word(&word);
+    if json {
+        let letters: Vec<_> = word.chars()
+            .map(|c| json!({ "letter": c, "score": language.letter(c).map_or(0, |letter| letter.score) }))
+            .collect();
+        print_json(json!({ "word": word, "score": score, "valid": valid, "letters": letters }));
+    } else {
+        let note = if valid { "" } else { " (not a playable word)" };
+        println!("{}: {}{}", display_word(&word), score, note);
+    }
+    Ok(0)
+}
+
+/// Exits with 1 when the word can't be played, for scripts.
+pub fn check(language: &'static Language, word: &str, json: bool) -> Result<i32, String> {
+    let word = normalize(language, word)?;
+    let run = open(language)?;
+    let in_dictionary = run.words_db().contains(&word);
+    let valid = run.is_valid_word(&word);
+    if json {
+        print_json(json!({ "word": word, "in_dictionary": in_dictionary, "valid": valid }));
+    } else if valid {
+        println!("{} is a word", display_word(&word));
+    } else if in_dictionary {
+        println!("{} is in the dictionary but can't be played", display_word(&word));
+    } else {
+        println!("{} is not a word", display_word(&word));
+    }
+    Ok(if valid { 0 } else { 1 })
+}
+
+pub fn solve(language: &'static Language, letters: &str, top: usize, json: bool) -> Result<i32, String> {
+    let letters = normalize(language, letters)?;
+    let mut run = open(language)?;
+    run.rack = letters.chars().collect();
+    let words = run.top_words(top);
+    if json {
+        let words: Vec<_> = words.iter().map(|(word, score)| json!({ "word": word, "score": score })).collect();
+        print_json(json!({ "letters": letters, "words": words }));
+    } else if words.is_empty() {
+        println!("No words in {}", display_word(&letters));
+    } else {
+        for (word, score) in words {
+            println!("{:>6}  {}", score, display_word(word));
+        }
+    }
+    Ok(0)
+}
+
+pub fn stats(language: &'static Language, json: bool) -> Result<i32, String> {
+    let run = open(language)?;
+    let words = run.words_db();
+    let mut by_length: BTreeMap<usize, usize> = BTreeMap::new();
+    for word in words {
+        *by_length.entry(word.chars().count()).or_default() += 1;
+    }
+    let total_letters: usize = by_length.iter().map(|(length, count)| length * count).sum();
+    let average_length = total_letters as f64 / words.len().max(1) as f64;
+    let best = words.iter()
+        .filter(|word| run.is_valid_word(word))
+        .map(|word| (word, run.word_score(word)))
+        .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(a.0)));
+
+    if json {
+        print_json(json!({
+            "language": language.name,
+            "words": words.len(),
+            "average_length": average_length,
+            "by_length": by_length,
+            "best_word": best.map(|(word, score)| json!({ "word": word, "score": score })),
+        }));
+    } else {
+        println!("{}: {} words, {:.1} letters on average", language.name, words.len(), average_length);
+        for (length, count) in &by_length {
+            println!("{:>4} letters: {}", length, count);
+        }
+        if let Some((word, score)) = best {
+            println!("Best word: {} ({})", display_word(word), score);
+        }
+    }
+    Ok(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn words_are_lower_cased_and_checked_against_the_tiles() {
+        let english = i18n::language("English").unwrap();
+        let german = i18n::language("Deutsch").unwrap();
+        assert_eq!(normalize(english, "Quiz"), Ok("quiz".to_string()));
+        assert_eq!(normalize(german, "GRÜẞE"), Ok("grüße".to_string()));
+        assert_eq!(normalize(english, "grüße"), Err("English has no Ü tile".to_string()));
+    }
+
+    #[test]
+    fn words_are_shown_in_capitals() {
+        assert_eq!(display_word("straße"), "STRAßE");
+    }
+
+    #[test]
+    fn solving_ranks_by_score_then_alphabetically() {
+        let words_db: HashSet<String> = ["cat", "act", "at", "dog"].iter().map(|word| word.to_string()).collect();
+        let mut run = Run::new(GameMode::Standard, i18n::language("English").unwrap(), Arc::new(words_db),
+                               Ruleset::default(), Loadout::default(), false);
+        run.rack = "tac".chars().collect();
+        let words: Vec<&str> = run.top_words(10).into_iter().map(|(word, _)| word.as_str()).collect();
+        assert_eq!(words, ["act", "cat", "at"]);
+        assert_eq!(run.top_words(1).len(), 1);
+    }
+}
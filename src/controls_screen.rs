@@ -0,0 +1,146 @@
+use macroquad::prelude::*;
+
+use crate::i18n::{tr, tr_args};
+use crate::input::{self, Action, KeyBindings};
+use crate::scene::{Context, Scene, Transition};
+use crate::settings;
+use crate::ui::button::Button;
+use crate::ui::layout;
+use crate::ui::theme;
+use crate::ui::widget::{self, Widget};
+
+pub struct ControlsScreen {
+    /// The action waiting for a new key after its button was clicked.
+    rebinding: Option<Action>,
+    /// One button per action, in the order of `Action::ALL`.
+    action_buttons: Vec<Button>,
+    reset_button: Button,
+    back_button: Button,
+}
+
+impl ControlsScreen {
+    pub fn new() -> ControlsScreen {
+        let theme = theme::current();
+        let column_width = 420.0;
+        let action_buttons = (0..Action::ALL.len())
+            .map(|i| {
+                let x = layout::width() / 2.0 - column_width - 10.0 + (i / 6) as f32 * (column_width + 20.0);
+                let y = 140.0 + (i % 6) as f32 * 70.0;
+                Button::new(x, y, column_width, 55.0, theme.panel, String::new(), 30.0)
+            })
+            .collect();
+
+        let mut screen = ControlsScreen {
+            rebinding: None,
+            action_buttons,
+            reset_button: Button::new(
+                layout::width() / 2.0 - 210.0,
+                layout::height() - 90.0,
+                200.0,
+                50.0,
+                theme.button,
+                tr("controls.defaults").to_string(),
+                35.0
+            ),
+            back_button: Button::new(
+                layout::width() / 2.0 + 10.0,
+                layout::height() - 90.0,
+                200.0,
+                50.0,
+                theme.button,
+                tr("common.back").to_string(),
+                40.0
+            ),
+        };
+        screen.refresh();
+        screen
+    }
+
+    fn refresh(&mut self) {
+        let bindings = settings::get().key_bindings.clone();
+        let theme = theme::current();
+        for (action, button) in Action::ALL.iter().zip(&mut self.action_buttons) {
+            let waiting = self.rebinding == Some(*action);
+            button.set_color(if waiting { theme.focus } else { theme.panel });
+            if waiting {
+                button.set_text(&format!("{}: ...", action.name()));
+            } else {
+                button.set_text(&format!("{}: {}", action.name(), input::key_name(bindings.key(*action))));
+            }
+        }
+    }
+
+    /// Arrow keys and Space are valid bindings, so navigation pauses while
+    /// waiting for a key and only the mouse can pick another action.
+    fn wait_for_key(&mut self, action: Action) {
+        if let Some(key) = get_last_key_pressed().filter(|key| input::is_bindable(*key)) {
+            settings::update(|settings| settings.key_bindings.bind(action, key));
+            self.rebinding = None;
+            return;
+        }
+        let clicked = self.action_buttons.iter().position(|button| button.is_clicked());
+        if let Some(i) = clicked {
+            let other = Action::ALL[i];
+            self.rebinding = if other == action { None } else { Some(other) };
+        }
+    }
+}
+
+impl Scene for ControlsScreen {
+    fn handle_input(&mut self, _ctx: &mut Context) -> Transition {
+        if let Some(action) = self.rebinding {
+            self.wait_for_key(action);
+            self.refresh();
+            return Transition::None;
+        }
+
+        // Actions fill two columns, but focus moves in reading order.
+        let mut focusable: Vec<&mut dyn Widget> = Vec::new();
+        let (left, right) = self.action_buttons.split_at_mut(6);
+        for (left, right) in left.iter_mut().zip(right.iter_mut()) {
+            focusable.push(left);
+            focusable.push(right);
+        }
+        focusable.push(&mut self.reset_button);
+        focusable.push(&mut self.back_button);
+        widget::focus(&mut focusable);
+
+        for (action, button) in Action::ALL.iter().zip(&mut self.action_buttons) {
+            if button.update() {
+                self.rebinding = Some(*action);
+            }
+        }
+        if self.reset_button.update() {
+            settings::update(|settings| settings.key_bindings = KeyBindings::default());
+        }
+        self.refresh();
+
+        if self.back_button.update() || input::is_back_pressed() {
+            return Transition::Pop;
+        }
+        Transition::None
+    }
+
+    fn draw(&self, _ctx: &Context) {
+        let theme = theme::current();
+        layout::clear(theme.background);
+
+        let title = tr("controls.title");
+        let title_dim = theme::measure_text(title, 60);
+        theme::draw_text(title, layout::width() / 2.0 - title_dim.width / 2.0, 70.0, 60.0, theme.text);
+
+        let help = match self.rebinding {
+            Some(action) => tr_args("controls.press_key", &[("action", &action.name())]),
+            None => tr("controls.help").to_string(),
+        };
+        let help_dim = theme::measure_text(&help, 30);
+        theme::draw_text(&help, layout::width() / 2.0 - help_dim.width / 2.0, 110.0, 30.0, theme.text);
+
+        for button in &self.action_buttons {
+            button.draw();
+        }
+        if self.rebinding.is_none() {
+            widget::draw_all(&[&self.reset_button, &self.back_button]);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! Crash reports. A panic writes a JSON report to `crashes` in the data
+//! directory, with the version, the panic and its backtrace, the run being
+//! played and the last log lines. The next launch tells the player where
+//! it is. Only the desktop build writes reports.
+
+use std::backtrace::Backtrace;
+use std::fs;
+use std::panic::{self, PanicHookInfo};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+
+use ci210_word_game::logging;
+use ci210_word_game::rules::{Observation, Run};
+use serde::Serialize;
+use serde_json::json;
+
+use crate::paths;
+use crate::storage::{self, Area};
+
+/// Remembers the report of the last crash until the player has been told.
+const PENDING_FILE: &str = "pending_crash.txt";
+
+/// Actions of the run kept in a report, the most recent ones.
+const RECENT_ACTIONS: usize = 50;
+
+#[derive(Clone, Serialize)]
+struct RunSnapshot {
+    language: String,
+    modifiers: Vec<String>,
+    #[serde(flatten)]
+    observation: Observation,
+    recent_actions: Vec<String>,
+}
+
+static RUN: Mutex<Option<RunSnapshot>> = Mutex::new(None);
+
+/// Notes the state of the run being played, for a report if the game
+/// crashes. Called whenever the run changes.
+pub fn record_run(run: &Run) {
+    let start = run.actions.len().saturating_sub(RECENT_ACTIONS);
+    let snapshot = RunSnapshot {
+        language: run.language.name.clone(),
+        modifiers: run.modifiers.iter().map(|modifier| format!("{:?}", modifier)).collect(),
+        observation: run.observe(),
+        recent_actions: run.actions[start..].to_vec(),
+    };
+    *RUN.lock().unwrap() = Some(snapshot);
+}
+
+fn crashes_dir() -> PathBuf {
+    paths::data_dir().join("crashes")
+}
+
+fn write_report(info: &PanicHookInfo) -> Result<PathBuf, String> {
+    let message = info.payload().downcast_ref::<&str>().map(|message| message.to_string())
+        .or_else(|| info.payload().downcast_ref::<String>().cloned())
+        .unwrap_or_default();
+    let location = info.location().map(|location| location.to_string());
+    // The panic may have happened while the run was being recorded.
+    let run = RUN.try_lock().ok().and_then(|run| run.clone());
+    let now = chrono::Local::now();
+    let report = json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "time": now.format("%Y-%m-%d %H:%M:%S").to_string(),
+        "os": std::env::consts::OS,
+        "panic": message,
+        "location": location,
+        "backtrace": Backtrace::force_capture().to_string().lines().collect::<Vec<_>>(),
+        "run": run,
+        "log_dir": logging::log_dir(),
+        "log": logging::recent(),
+    });
+
+    let dir = crashes_dir();
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let path = dir.join(format!("crash-{}.json", now.format("%Y%m%d-%H%M%S")));
+    let serialized = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+    fs::write(&path, serialized).map_err(|e| e.to_string())?;
+    storage::get().write(Area::Data, PENDING_FILE, &path.display().to_string())?;
+    Ok(path)
+}
+
+/// Writes a report on any panic, then lets the default hook print it. The
+/// hook doesn't log, as the panic may have happened inside the logger.
+pub fn init() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        match write_report(info) {
+            Ok(path) => eprintln!("Crash report saved to {}", path.display()),
+            Err(e) => eprintln!("Error: Failed to write crash report: {}", e),
+        }
+        default_hook(info);
+    }));
+}
+
+/// The report of a crash the player hasn't been told about yet. Asking
+/// forgets it, so the player is told once.
+pub fn take_pending() -> Option<PathBuf> {
+    let path = storage::get().read(Area::Data, PENDING_FILE)?;
+    storage::get().remove(Area::Data, PENDING_FILE);
+    Some(PathBuf::from(path.trim()))
+}
+
+/// Opens the folder holding `report` in the system's file manager.
+pub fn show_report(report: &Path) {
+    let dir = report.parent().map_or_else(crashes_dir, Path::to_path_buf);
+    let program = if cfg!(windows) {
+        "explorer"
+    } else if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    if let Err(e) = Command::new(program).arg(&dir).spawn() {
+        tracing::warn!(dir = %dir.display(), "Failed to open the crash report folder: {}", e);
+    }
+}
@@ -0,0 +1,137 @@
+//! The developer overlay, compiled in with the `dev` feature. F1 or the
+//! backtick key opens it over the game. It shows the frame rate, the scene
+//! on top and the state of the run, and has a console for cheats. The game
+//! is paused while it's open and the keyboard goes to the console.
+
+use std::cell::RefCell;
+
+use macroquad::prelude::*;
+
+use crate::input;
+use crate::scene::{Context, SceneStack};
+use crate::ui::layout;
+use crate::ui::theme;
+
+const HELP: &str = "setscore <n>, round <n>, rack <letters>, give <modifier>, win, clear, help";
+
+/// Console lines kept on screen.
+const MAX_OUTPUT: usize = 8;
+
+const FONT_SIZE: f32 = 22.0;
+const LINE_HEIGHT: f32 = 26.0;
+
+#[derive(Default)]
+struct Console {
+    open: bool,
+    input: String,
+    output: Vec<String>,
+}
+
+thread_local! {
+    static CONSOLE: RefCell<Console> = RefCell::new(Console::default());
+}
+
+impl Console {
+    fn print(&mut self, line: String) {
+        self.output.push(line);
+        let extra = self.output.len().saturating_sub(MAX_OUTPUT);
+        self.output.drain(..extra);
+    }
+
+    /// Runs a command. The scenes get it from the top of the stack down
+    /// until one of them knows it.
+    fn run(&mut self, command: &str, scenes: &mut SceneStack) {
+        self.print(format!("> {}", command));
+        let args: Vec<&str> = command.split_whitespace().collect();
+        match args.as_slice() {
+            [] => {}
+            ["help"] => self.print(HELP.to_string()),
+            ["clear"] => self.output.clear(),
+            _ => {
+                let result = scenes.scenes_mut().find_map(|scene| scene.dev_command(&args));
+                match result {
+                    Some(Ok(message)) => self.print(message),
+                    Some(Err(e)) => self.print(format!("Error: {}", e)),
+                    None => self.print(format!("Unknown command here: {}", args[0])),
+                }
+                // The game only notices the win once it runs again.
+                if args[0] == "win" {
+                    self.open = false;
+                }
+            }
+        }
+    }
+
+    /// Reads this frame's typing. Returns a finished command.
+    fn read_input(&mut self) -> Option<String> {
+        for c in input::typed_chars() {
+            if c != '`' && !c.is_control() {
+                self.input.push(c);
+            }
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            self.input.pop();
+        }
+        if is_key_pressed(KeyCode::Escape) {
+            self.open = false;
+        }
+        if is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::KpEnter) {
+            return Some(std::mem::take(&mut self.input));
+        }
+        None
+    }
+}
+
+/// Runs one frame of the game, or while the overlay is open, draws the game
+/// without running it and handles the console instead. Returns `false` once
+/// the game should quit, like `SceneStack::frame`.
+pub fn frame(scenes: &mut SceneStack, ctx: &mut Context) -> bool {
+    let paused = CONSOLE.with(|console| {
+        let console = &mut *console.borrow_mut();
+        if is_key_pressed(KeyCode::F1) || is_key_pressed(KeyCode::GraveAccent) {
+            // The key that opens or closes the console does nothing else.
+            console.open = !console.open;
+            return true;
+        }
+        if console.open {
+            if let Some(command) = console.read_input() {
+                console.run(&command, scenes);
+            }
+        }
+        console.open
+    });
+    if !paused {
+        return scenes.frame(ctx);
+    }
+    scenes.draw(ctx);
+    if CONSOLE.with(|console| console.borrow().open) {
+        draw(scenes);
+    }
+    true
+}
+
+fn draw(scenes: &mut SceneStack) {
+    let mut lines = vec![format!("FPS: {}", get_fps())];
+    let mut fields = Vec::new();
+    for (i, scene) in scenes.scenes_mut().enumerate() {
+        if i == 0 {
+            lines.push(format!("Scene: {}", scene.dev_name()));
+        }
+        if fields.is_empty() {
+            fields = scene.dev_fields();
+        }
+    }
+    lines.extend(fields.into_iter().map(|(name, value)| format!("{}: {}", name, value)));
+    lines.push(String::new());
+    CONSOLE.with(|console| {
+        let console = console.borrow();
+        lines.extend(console.output.iter().cloned());
+        lines.push(format!("> {}_", console.input));
+    });
+
+    let height = lines.len() as f32 * LINE_HEIGHT + 20.0;
+    draw_rectangle(0.0, 0.0, layout::width(), height, Color::new(0.0, 0.0, 0.0, 0.8));
+    for (i, line) in lines.iter().enumerate() {
+        theme::draw_text(line, 15.0, 30.0 + i as f32 * LINE_HEIGHT, FONT_SIZE, WHITE);
+    }
+}
@@ -0,0 +1,72 @@
+//! Loads dictionaries in the background, so the window keeps drawing while
+//! a large one is parsed. On the desktop a thread reads the file; in the
+//! browser it's fetched from the server.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+use crate::i18n::Language;
+
+type Loaded = Result<HashSet<String>, String>;
+
+/// Where the loader leaves its result.
+type Slot = Arc<Mutex<Option<Loaded>>>;
+
+enum State {
+    Loading(Slot),
+    Ready(Arc<HashSet<String>>),
+}
+
+#[derive(Default)]
+pub struct Dictionaries {
+    by_language: HashMap<String, State>,
+}
+
+impl Dictionaries {
+    /// The dictionary of `language` once it's loaded, or `None` while it's
+    /// loading. Asking for one that isn't loaded starts loading it. A failed
+    /// load is reported once and tried again the next time it's asked for.
+    pub fn poll(&mut self, language: &'static Language) -> Option<Result<Arc<HashSet<String>>, String>> {
+        let state = self.by_language.entry(language.name.clone()).or_insert_with(|| {
+            let slot = Slot::default();
+            start_loading(language, slot.clone());
+            State::Loading(slot)
+        });
+        let loaded = match state {
+            State::Ready(words_db) => return Some(Ok(words_db.clone())),
+            State::Loading(slot) => slot.lock().unwrap().take()?,
+        };
+        match loaded {
+            Ok(words_db) => {
+                let words_db = Arc::new(words_db);
+                *state = State::Ready(words_db.clone());
+                Some(Ok(words_db))
+            }
+            Err(e) => {
+                self.by_language.remove(&language.name);
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn start_loading(language: &'static Language, slot: Slot) {
+    std::thread::spawn(move || {
+        let loaded = crate::rules::initialize_words_db(language);
+        *slot.lock().unwrap() = Some(loaded);
+    });
+}
+
+#[cfg(target_arch = "wasm32")]
+fn start_loading(language: &'static Language, slot: Slot) {
+    use macroquad::experimental::coroutines::start_coroutine;
+
+    start_coroutine(async move {
+        let loaded = macroquad::file::load_string(&language.dictionary).await
+            .map_err(|e| format!("Could not fetch {}: {}", language.dictionary, e))
+            .and_then(|contents| ci210_word_game::json::words_from_str(&contents))
+            .map(HashSet::from_iter);
+        *slot.lock().unwrap() = Some(loaded);
+    });
+}
@@ -0,0 +1,84 @@
+use macroquad::prelude::*;
+
+use crate::i18n::tr;
+use crate::scene::{Context, Scene, Transition};
+use crate::title::TitleScreen;
+use crate::ui::button::Button;
+use crate::ui::label::Label;
+use crate::ui::layout::{self, Anchor, Rect};
+use crate::ui::theme;
+use crate::ui::widget;
+
+const DETAIL_FONT_SIZE: f32 = 22.0;
+
+/// Shown instead of crashing when something the game needs can't be
+/// loaded. The player can go back to the title screen, e.g. to pick
+/// another language, or quit.
+pub struct ErrorScreen {
+    title: Label,
+    message: Label,
+    /// The error itself, wrapped to fit the screen.
+    detail: Vec<String>,
+    title_screen: Button,
+    quit: Button,
+}
+
+impl ErrorScreen {
+    pub fn new(message: &str, detail: &str) -> ErrorScreen {
+        tracing::error!(detail, "{}", message);
+        let theme = theme::current();
+        let buttons = Rect::screen().anchored(Anchor::Bottom, 520.0, 50.0)
+            .offset(0.0, -80.0)
+            .columns(2, 20.0);
+        ErrorScreen {
+            title: Label::new(Rect::new(0.0, 80.0, layout::width(), 80.0), tr("error.title"), 60.0)
+                .with_color(theme.panel_text),
+            message: Label::new(Rect::new(0.0, 190.0, layout::width(), 40.0), message, 32.0)
+                .with_color(theme.panel_text),
+            detail: wrap(detail, layout::width() - 200.0),
+            title_screen: Button::from_rect(buttons[0], theme.button, tr("error.title_screen").to_string(), 35.0),
+            quit: Button::from_rect(buttons[1], theme.danger, tr("common.quit").to_string(), 35.0),
+        }
+    }
+}
+
+/// Splits `text` into lines no wider than `width` where it can, breaking
+/// between words.
+fn wrap(text: &str, width: f32) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for word in text.split_whitespace() {
+        match lines.last_mut() {
+            Some(line) if theme::measure_text(&format!("{} {}", line, word), DETAIL_FONT_SIZE as u16).width
+                <= width => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.to_string()),
+        }
+    }
+    lines
+}
+
+impl Scene for ErrorScreen {
+    fn handle_input(&mut self, _ctx: &mut Context) -> Transition {
+        widget::focus(&mut [&mut self.title_screen, &mut self.quit]);
+        if self.title_screen.update() || is_key_pressed(KeyCode::Enter) {
+            return Transition::Reset(Box::new(TitleScreen::new()));
+        }
+        if self.quit.update() {
+            return Transition::Quit;
+        }
+        Transition::None
+    }
+
+    fn draw(&self, _ctx: &Context) {
+        let theme = theme::current();
+        layout::clear(theme.lose_background);
+        widget::draw_all(&[&self.title, &self.message]);
+        for (i, line) in self.detail.iter().enumerate() {
+            layout::draw_centered_text(line, layout::width() / 2.0, 280.0 + i as f32 * 32.0, DETAIL_FONT_SIZE,
+                                       theme.panel_text);
+        }
+        widget::draw_all(&[&self.title_screen, &self.quit]);
+    }
+}
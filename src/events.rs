@@ -0,0 +1,57 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+
+use crate::mode::GameMode;
+
+/// Something that happened in the game logic that other systems may want to
+/// react to.
+#[derive(Clone, Debug)]
+pub enum GameEvent {
+    /// `rare` is whether the word has a rare letter of the run's language,
+    /// which needn't be the language the game is shown in.
+    WordScored { word: String, score: i32, rare: bool },
+    WordRejected { word: String },
+    Shuffled,
+    Discarded,
+    RoundWon { mode: GameMode, round: u32, words_used: u32 },
+    RunWon { mode: GameMode, score: i32, discards_used: u32 },
+    RunLost { mode: GameMode, round: u32, score: i32 },
+}
+
+type Subscriber = Box<dyn FnMut(&GameEvent) + Send>;
+
+static QUEUE: Mutex<Vec<GameEvent>> = Mutex::new(Vec::new());
+static SUBSCRIBERS: Lazy<Mutex<Vec<Subscriber>>> = Lazy::new(|| Mutex::new(Vec::new()));
+/// Set by the first `subscribe`. Until then nothing is queued, so tools
+/// that play runs without listening don't pile events up.
+static LISTENING: AtomicBool = AtomicBool::new(false);
+
+/// Queues an event. Events are delivered on the next `dispatch`, so it is
+/// safe to publish while holding game state locks.
+pub fn publish(event: GameEvent) {
+    if LISTENING.load(Ordering::Relaxed) {
+        QUEUE.lock().unwrap().push(event);
+    }
+}
+
+pub fn subscribe(subscriber: impl FnMut(&GameEvent) + Send + 'static) {
+    SUBSCRIBERS.lock().unwrap().push(Box::new(subscriber));
+    LISTENING.store(true, Ordering::Relaxed);
+}
+
+/// Delivers every queued event to every subscriber. Called once per frame.
+pub fn dispatch() {
+    let events: Vec<GameEvent> = std::mem::take(&mut *QUEUE.lock().unwrap());
+    if events.is_empty() {
+        return;
+    }
+
+    let mut subscribers = SUBSCRIBERS.lock().unwrap();
+    for event in &events {
+        for subscriber in subscribers.iter_mut() {
+            subscriber(event);
+        }
+    }
+}
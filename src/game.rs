@@ -1,452 +1,875 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
 use macroquad::prelude::*;
 
-use std::collections::{HashMap, HashSet};
-use std::fs;
-use std::fs::File;
-use std::io::Write;
-use ::rand::Rng;
-use once_cell::sync::Lazy;
+use crate::achievements;
+use crate::audio::{self, Music, Sound};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::crash;
+use crate::i18n::{self, tr, tr_args, Language};
+use crate::input::{self, Action};
 use crate::ui;
-use crate::json;
-use crate::ui::screen::{Screen, ScreenManager};
-
-static mut game_state: Lazy<GameState> = Lazy::new(|| GameState::new());
-
-#[derive(Default)]
+use crate::leaderboard::PendingRun;
+use crate::loading_screen;
+use crate::lose_screen::LoseScreen;
+use crate::pause_screen::PauseScreen;
+use crate::round_win_screen::RoundWinScreen;
+use crate::rules::{Hint, Outcome, Run};
+use crate::saved_run::SavedRun;
+use crate::win_screen::WinScreen;
+use crate::scene::{Context, Scene, Transition};
+use crate::settings;
+use crate::ui::button::Button;
+use crate::ui::anim::{Particles, Shake, Tween};
+use crate::ui::focus;
+use crate::ui::label;
+use crate::ui::pointer;
+use crate::ui::layout::{self, Anchor, Rect};
+use crate::ui::theme;
+use crate::ui::toast;
+
+const LETTER_SLOT_WIDTH: f32 = 36.0;
+
+/// Focus navigation covers the 3x4 rack first, then the buttons below it.
+const FOCUS_ROWS: [usize; 5] = [4, 4, 4, 3, 2];
+const FOCUS_HINT: usize = 12;
+const FOCUS_PLAY: usize = 13;
+const FOCUS_CLEAR: usize = 14;
+const FOCUS_SHUFFLE: usize = 15;
+const FOCUS_DISCARD: usize = 16;
+
+/// How often the round score counts up towards a new total.
+const SCORE_TICK_SECONDS: f32 = 0.04;
+
+/// Words worth at least this much burst into confetti.
+const BIG_SCORE: i32 = 500;
+
+/// How far the pointer has to move before a press becomes a drag.
+const DRAG_DISTANCE: f32 = 5.0;
+
+/// A run and the screen's view of it: one tile per letter on the rack and
+/// the word being built from them.
 struct GameState {
-    scoring: HashMap<char, i32>,
-    words: Vec<char>,
+    run: Run,
     tiles: Vec<ui::tile::Tile>,
     current_word: String,
-    round_score: u64,
-    total_score: i32,
-    words_db: HashSet<String>,
-    words_remaining: u32,
-    discards: u32,
-    round: u32,
-    guessed_words: HashSet<String>,
-    filtered_words: HashSet<String>,
+    /// Indexes into `tiles`, in the order the letters appear in the word.
+    selected: Vec<usize>,
+    word_drag: Option<Drag>,
+    tile_drag: Option<Drag>,
 }
 
-impl GameState {
-    fn new() -> Self {
-        let scoring = {
-            let mut map = HashMap::new();
-            map.insert('e', 10); map.insert('a', 10); map.insert('t', 10);
-            map.insert('h', 15); map.insert('i', 15); map.insert('n', 15);
-            map.insert('o', 15); map.insert('s', 15); map.insert('r', 15);
-            map.insert('d', 20); map.insert('l', 20);
-            map.insert('u', 25); map.insert('f', 25); map.insert('m', 25);
-            map.insert('c', 25); map.insert('g', 25); map.insert('y', 25);
-            map.insert('p', 30); map.insert('b', 30); map.insert('w', 30);
-            map.insert('v', 35); map.insert('k', 35); map.insert('j', 35);
-            map.insert('x', 35); map.insert('q', 35); map.insert('z', 35);
-            map
-        };
-
-        let words: Vec<char> = scoring.keys().cloned().collect();
-
-        let words_db = initialize_words_db()
-            .expect("Failed to initialize words database: Could not load or deserialize the cache");
-
-        let filtered_words = ('a'..='z').map(|c| c.to_string()).collect();
+/// A letter flying from its tile on the rack into its slot in the word box.
+/// The word box leaves the slot empty until it lands.
+struct Flight {
+    tile_index: usize,
+    tween: Tween,
+}
 
+/// A letter in the word box or a tile on the rack that the pointer went
+/// down on. It becomes a drag once the pointer moves, otherwise releasing
+/// it counts as a tap.
+struct Drag {
+    /// The letter's position in the word, or the tile's index on the rack.
+    from: usize,
+    start: (f32, f32),
+    dragging: bool,
+}
+
+impl Drag {
+    fn new(from: usize, start: (f32, f32)) -> Drag {
+        Drag { from, start, dragging: false }
+    }
+
+    fn track(&mut self, pointer: (f32, f32)) {
+        let moved = (pointer.0 - self.start.0).abs() + (pointer.1 - self.start.1).abs();
+        self.dragging |= moved > DRAG_DISTANCE;
+    }
+}
+
+/// Where everything on the game screen goes on the virtual canvas: the HUD
+/// panel on the left, the rack and word controls on the right.
+struct GameLayout {
+    hud: Rect,
+    target: Rect,
+    round_score: Rect,
+    words: Rect,
+    discards: Rect,
+    round: Rect,
+    tiles: Vec<Rect>,
+    word_box: Rect,
+    hint: Rect,
+    play: Rect,
+    clear: Rect,
+    shuffle: Rect,
+    discard: Rect,
+}
+
+impl GameLayout {
+    fn new() -> GameLayout {
+        let hud = Rect::new(100.0, 50.0, 450.0, 600.0);
+        let hud_inner = hud.pad(25.0);
+        let counters = hud_inner.anchored(Anchor::Top, 300.0, 75.0).offset(0.0, 385.0).columns(2, 50.0);
+
+        let board = Rect::new(layout::width() / 2.0 + 50.0, 100.0, 475.0, 350.0);
+        let word_box = Rect::new(board.x, layout::height() - 225.0, board.w, 50.0);
+        let button_width = board.w / 3.0;
+        let word_row = word_box.offset(0.0, 75.0);
+        let rack_row = word_row.offset(0.0, 75.0);
+
+        GameLayout {
+            hud,
+            target: hud_inner.anchored(Anchor::Top, 400.0, 150.0),
+            round_score: hud_inner.anchored(Anchor::Top, 400.0, 200.0).offset(0.0, 175.0),
+            words: counters[0],
+            discards: counters[1],
+            round: hud_inner.anchored(Anchor::Top, 125.0, 75.0).offset(0.0, 485.0),
+            tiles: board.grid(3, 4, 25.0),
+            word_box,
+            hint: Rect::new(word_row.x + 10.0, word_row.y, 125.0, 50.0),
+            play: Rect::new(word_row.x + 150.0, word_row.y, button_width, 50.0),
+            clear: word_row.anchored(Anchor::Right, 50.0, 50.0).offset(-10.0, 0.0),
+            shuffle: Rect::new(rack_row.x + 60.0, rack_row.y, button_width, 50.0),
+            discard: Rect::new(rack_row.x + 235.0, rack_row.y, button_width, 50.0),
+        }
+    }
+}
+
+impl GameState {
+    fn new(run: Run) -> Self {
         GameState {
-            scoring,
-            words,
+            run,
             tiles: Vec::new(),
             current_word: String::new(),
-            round_score: 750,
-            total_score: 0,
-            words_db,
-            words_remaining: 4,
-            discards: 3,
-            round: 1,
-            guessed_words: HashSet::new(),
-            filtered_words,
+            selected: Vec::new(),
+            word_drag: None,
+            tile_drag: None,
         }
     }
+
+    fn sync_word(&mut self) {
+        self.current_word = self.selected.iter()
+            .map(|&i| self.tiles[i].get_letter())
+            .collect();
+    }
+
+    /// Puts the run aside for the pause menu's Save & Quit. The word being
+    /// built isn't kept.
+    fn save(&self) -> Result<(), String> {
+        self.run.save().save()
+    }
+
+    /// Notes the run for a crash report. Called whenever the run changes.
+    fn record(&self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        crash::record_run(&self.run);
+    }
+
+    /// Lays the rack of a restored run out in place.
+    fn place_tiles(&mut self, game_layout: &GameLayout) {
+        let color = theme::skin_color(self.run.skin);
+        self.tiles = self.run.rack.iter().zip(&game_layout.tiles)
+            .map(|(&letter, &rect)| ui::tile::Tile::new(letter, rect, color))
+            .collect();
+    }
+
+    /// The finished run, ready for the player to put a name on it.
+    fn finish_run(&self) -> PendingRun {
+        let run = &self.run;
+        PendingRun::new(run.run_score, run.mode, run.seed, run.round, run.actions.join(","), run.cheated)
+    }
+}
+
+pub struct GameScreen {
+    state: GameState,
+    layout: GameLayout,
+    play: Button,
+    clear: Button,
+    hint: Button,
+    shuffle: Button,
+    discard: Button,
+    /// The round score shown in the HUD, which counts up to the real one.
+    shown_score: i32,
+    tick_timer: f32,
+    flights: Vec<Flight>,
+    /// Discarded tiles on their way off the screen.
+    leaving: Vec<ui::tile::Tile>,
+    word_shake: Shake,
+    screen_shake: Shake,
+    particles: Particles,
+    /// Set once the run is saved from the pause menu, to quit on the next frame.
+    saved: bool,
 }
 
-fn initialize_words_db() -> Result<HashSet<String>, String> {
-    let cache_file = "src/words_cache.json";
+impl GameScreen {
+    /// A fresh run in the mode picked on the title screen, with the seed and
+    /// ruleset from the command line if it gave any. Shows the loading
+    /// screen first while the language's dictionary isn't loaded yet.
+    pub fn start(ctx: &mut Context) -> Box<dyn Scene> {
+        loading_screen::when_loaded(ctx, i18n::current(), |ctx, words_db| Box::new(GameScreen::new(ctx, words_db)))
+    }
+
+    /// Continues a run saved from the pause menu, in the language it was
+    /// started in.
+    pub fn continue_run(ctx: &mut Context, saved: SavedRun) -> Box<dyn Scene> {
+        let language = i18n::language(&saved.language).unwrap_or_else(i18n::current);
+        loading_screen::when_loaded(ctx, language, move |_, words_db| {
+            Box::new(GameScreen::restore(saved, language, words_db))
+        })
+    }
+
+    fn new(ctx: &mut Context, words_db: Arc<HashSet<String>>) -> GameScreen {
+        let game_layout = GameLayout::new();
+        let mut run = Run::new(ctx.mode, i18n::current(), words_db, ctx.ruleset.clone(),
+                               achievements::loadout(), settings::get().free_invalid_words);
+        if let Some(seed) = ctx.seed {
+            run.set_seed(seed);
+        }
+        tracing::info!(mode = ?run.mode, seed = run.seed, language = %run.language.name, "Run started");
+        achievements::count_run(true);
+        let mut state = GameState::new(run);
+        deal_tiles(&mut state, &game_layout);
+        GameScreen::with_state(state, game_layout)
+    }
+
+    fn restore(saved: SavedRun, language: &'static Language, words_db: Arc<HashSet<String>>) -> GameScreen {
+        let game_layout = GameLayout::new();
+        let mut state = GameState::new(Run::restore(saved, language, words_db));
+        // Only now that the run is back, so a failed load keeps the save.
+        SavedRun::delete();
+        let run = &state.run;
+        tracing::info!(mode = ?run.mode, seed = run.seed, round = run.round, "Run continued");
+        achievements::count_run(!run.cheated);
+        state.place_tiles(&game_layout);
+        state.record();
+        let mut screen = GameScreen::with_state(state, game_layout);
+        screen.shown_score = screen.state.run.total_score;
+        screen
+    }
+
+    fn with_state(state: GameState, game_layout: GameLayout) -> GameScreen {
+        let theme = theme::current();
+        GameScreen {
+            state,
+            play: Button::from_rect(game_layout.play, theme.play_button, tr("action.submit").to_string(), 35.0),
+            clear: Button::from_rect(game_layout.clear, theme.danger, "X".to_string(), 40.0),
+            hint: Button::from_rect(game_layout.hint, theme.hint_button, tr("action.hint").to_string(), 30.0),
+            shuffle: Button::from_rect(game_layout.shuffle, theme.shuffle_button,
+                                       tr("action.shuffle").to_string(), 40.0),
+            discard: Button::from_rect(game_layout.discard, theme.danger, tr("action.discard").to_string(), 40.0),
+            layout: game_layout,
+            shown_score: 0,
+            tick_timer: 0.0,
+            flights: Vec::new(),
+            leaving: Vec::new(),
+            word_shake: Shake::default(),
+            screen_shake: Shake::default(),
+            particles: Particles::default(),
+            saved: false,
+        }
+    }
+}
 
-    if let Ok(cache_contents) = fs::read_to_string(&cache_file) {
-        if let Ok(cached_words) = serde_json::from_str(&cache_contents) {
-            return Ok(cached_words);
+impl Scene for GameScreen {
+    /// Picks up theme changes made from the pause menu, and saves the run
+    /// if the player picked Save & Quit.
+    fn resume(&mut self, ctx: &mut Context) {
+        if std::mem::take(&mut ctx.save_requested) {
+            match self.state.save() {
+                Ok(()) => self.saved = true,
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    toast::show(tr("pause.save_failed").to_string());
+                }
+            }
+        }
+        let theme = theme::current();
+        self.play.set_color(theme.play_button);
+        self.clear.set_color(theme.danger);
+        self.hint.set_color(theme.hint_button);
+        self.shuffle.set_color(theme.shuffle_button);
+        self.discard.set_color(theme.danger);
+        let color = theme::skin_color(self.state.run.skin);
+        for tile in &mut self.state.tiles {
+            tile.set_color(color);
         }
     }
 
-    let db: HashSet<_> = HashSet::from_iter(json::get_available_words());
+    fn handle_input(&mut self, _ctx: &mut Context) -> Transition {
+        if self.saved {
+            return Transition::Quit;
+        }
+        if input::is_pressed(Action::Pause) {
+            return Transition::Push(Box::new(PauseScreen::new(self.state.run.mode)));
+        }
+        let game_state = &mut self.state;
 
-    match serde_json::to_string(&db) {
-        Ok(serialized) => {
-            match File::create(cache_file) {
-                Ok(mut file) => {
-                    if let Err(e) = file.write_all(serialized.as_bytes()) {
-                        return Err(format!("Failed to write to cache file: {}", e));
+        focus::begin(&FOCUS_ROWS);
+        for (tile_index, tile) in game_state.tiles.iter_mut().enumerate() {
+            tile.get_button().set_focused(focus::is_focused(tile_index));
+        }
+        self.hint.set_focused(focus::is_focused(FOCUS_HINT));
+        self.play.set_focused(focus::is_focused(FOCUS_PLAY));
+        self.clear.set_focused(focus::is_focused(FOCUS_CLEAR));
+        self.shuffle.set_focused(focus::is_focused(FOCUS_SHUFFLE));
+        self.discard.set_focused(focus::is_focused(FOCUS_DISCARD));
+
+        word_box_input(game_state, self.layout.word_box);
+        let selected_before = game_state.selected.clone();
+
+        let play_pressed = self.play.update() || focus::is_activated(FOCUS_PLAY)
+            || input::is_pressed(Action::Submit);
+        if !game_state.current_word.is_empty() && play_pressed {
+            let word_box = self.layout.word_box;
+            let word = game_state.current_word.clone();
+            match game_state.run.play_word(&word) {
+                Some(score) => {
+                    if score >= BIG_SCORE {
+                        let theme = theme::current();
+                        let colors = [theme.accent, theme.highlight, theme.focus, theme.success];
+                        self.particles.burst(word_box.center(), 80, &colors);
+                        self.screen_shake.start(8.0, 0.4);
                     }
-                },
-                Err(e) => return Err(format!("Failed to create or overwrite cache file: {}", e)),
+                }
+                None => self.word_shake.start(14.0, 0.35),
             }
-        },
-        Err(e) => return Err(format!("Failed to serialize empty words database: {}", e)),
+            game_state.record();
+            clear_word(game_state);
+        }
+
+        if self.clear.update() || focus::is_activated(FOCUS_CLEAR) || input::is_pressed(Action::ClearWord) {
+            clear_word(game_state);
+        } else if input::is_pressed(Action::RemoveLetter) {
+            if input::is_modifier_down() {
+                clear_word(game_state);
+            } else if !game_state.selected.is_empty() {
+                remove_letter(game_state, game_state.selected.len() - 1);
+            }
+        }
+
+        if self.hint.update() || focus::is_activated(FOCUS_HINT) || input::is_pressed(Action::Hint) {
+            show_hint(game_state);
+        }
+
+        // Both change tile indexes, so letters still in flight just land.
+        if self.shuffle.update() || focus::is_activated(FOCUS_SHUFFLE) || input::is_pressed(Action::Shuffle) {
+            shuffle_tiles(game_state, &self.layout);
+            self.flights.clear();
+        }
+
+        if self.discard.update() || focus::is_activated(FOCUS_DISCARD) || input::is_pressed(Action::Discard) {
+            for (i, mut tile) in discard_tiles(game_state).into_iter().enumerate() {
+                let rect = tile.rect();
+                tile.slide_to(Rect::new(rect.x, layout::height() + 20.0, rect.w, rect.h), i as f32 * 0.02);
+                self.leaving.push(tile);
+            }
+            self.flights.clear();
+        }
+
+        let dropped = rack_input(game_state, &self.layout);
+        let picked = game_state.tiles.iter().enumerate()
+            .position(|(i, t)| !t.is_used() && focus::is_activated(i));
+        if let Some(tile_index) = picked {
+            select_tile(game_state, tile_index);
+        }
+
+        // Each typed letter takes the first unused tile showing it, so racks
+        // with repeated letters only spend one tile per key press.
+        for letter in input::typed_letters() {
+            let unused = game_state.tiles.iter()
+                .position(|t| !t.is_used() && t.get_letter() == letter);
+            if let Some(tile_index) = unused {
+                select_tile(game_state, tile_index);
+            }
+        }
+
+        let word_box = self.layout.word_box;
+        let start_x = word_start_x(game_state, word_box);
+        for (position, &tile_index) in game_state.selected.iter().enumerate() {
+            if !selected_before.contains(&tile_index) && dropped != Some(tile_index) {
+                let slot = Rect::new(start_x + position as f32 * LETTER_SLOT_WIDTH, word_box.y,
+                                     LETTER_SLOT_WIDTH, word_box.h);
+                let from = game_state.tiles[tile_index].rect();
+                self.flights.push(Flight { tile_index, tween: Tween::new(from, slot, 0.2) });
+            }
+        }
+        Transition::None
+    }
+
+    #[cfg(feature = "dev")]
+    fn dev_fields(&self) -> Vec<(&'static str, String)> {
+        let run = &self.state.run;
+        let rack: String = run.rack.iter().collect();
+        vec![
+            ("mode", format!("{:?}", run.mode)),
+            ("seed", run.seed.to_string()),
+            ("round", run.round.to_string()),
+            ("score", format!("{} / {}", run.total_score, run.round_score)),
+            ("run score", run.run_score.to_string()),
+            ("words", run.words_remaining.to_string()),
+            ("discards", run.discards.to_string()),
+            ("hints", run.hints_left.to_string()),
+            ("rack", rack),
+            ("word", self.state.current_word.clone()),
+            ("modifiers", format!("{:?}", run.modifiers)),
+            ("time left", run.time_left.map_or("-".to_string(), |time| format!("{:.1}", time))),
+        ]
     }
 
-    Ok(db)
+    #[cfg(feature = "dev")]
+    fn dev_command(&mut self, args: &[&str]) -> Option<Result<String, String>> {
+        let result = dev_command(&mut self.state, &self.layout, args);
+        if let Some(Ok(_)) = result {
+            self.state.run.cheated = true;
+            achievements::count_run(false);
+        }
+        self.state.record();
+        result
+    }
+
+    fn update(&mut self, _ctx: &mut Context) -> Transition {
+        let transition = update(&mut self.state);
+        deal_tiles(&mut self.state, &self.layout);
+
+        let dt = get_frame_time();
+        for tile in self.state.tiles.iter_mut().chain(self.leaving.iter_mut()) {
+            tile.update(dt);
+        }
+        self.leaving.retain(|tile| tile.is_moving());
+        let selected = &self.state.selected;
+        self.flights.retain_mut(|flight| {
+            flight.tween.update(dt);
+            !flight.tween.is_done() && selected.contains(&flight.tile_index)
+        });
+        self.word_shake.update(dt);
+        self.screen_shake.update(dt);
+        self.particles.update(dt);
+
+        self.hint.set_text(&tr_args("game.hint", &[("count", &self.state.run.hints_left)]));
+        self.discard.set_enabled(self.state.run.discards > 0);
+
+        // Big jumps count up fast and slow down near the total.
+        let total = self.state.run.total_score;
+        self.tick_timer -= get_frame_time();
+        if self.shown_score > total {
+            self.shown_score = total;
+        } else if self.shown_score < total && self.tick_timer <= 0.0 {
+            self.shown_score += ((total - self.shown_score) / 4).max(1);
+            self.tick_timer = SCORE_TICK_SECONDS;
+            audio::play(Sound::ScoreTick);
+        }
+        transition
+    }
+
+    fn music(&self) -> Music {
+        Music::Game
+    }
+
+    fn draw(&self, _ctx: &Context) {
+        let theme = theme::current();
+        layout::clear(theme.background);
+
+        let base = layout::offset();
+        let shake = self.screen_shake.offset();
+        layout::set_offset(base.0 + shake.0, base.1 + shake.1);
+
+        draw_hud(&self.state.run, &self.layout, self.shown_score);
+        // A dragged tile goes on top of the rest.
+        let dragged = self.state.tile_drag.as_ref().filter(|drag| drag.dragging).map(|drag| drag.from);
+        for (tile_index, tile) in self.state.tiles.iter().enumerate() {
+            if dragged != Some(tile_index) {
+                tile.draw();
+            }
+        }
+        for tile in &self.leaving {
+            tile.draw();
+        }
+        let flying: Vec<usize> = self.flights.iter().map(|flight| flight.tile_index).collect();
+        let word_box = self.layout.word_box.offset(self.word_shake.offset().0, 0.0);
+        draw_word_box(&self.state, word_box, &flying);
+        for button in [&self.play, &self.clear, &self.hint, &self.shuffle, &self.discard] {
+            button.draw();
+        }
+        if let Some(tile_index) = dragged {
+            self.state.tiles[tile_index].draw();
+        }
+
+        let color = theme::skin_color(self.state.run.skin);
+        for flight in &self.flights {
+            let rect = flight.tween.rect();
+            let letter = self.state.tiles[flight.tile_index].get_letter().to_string();
+            theme::draw_rounded_rect(rect.x, rect.y, rect.w, rect.h, theme.tile_radius, color);
+            label::draw_aligned(&letter, rect, Anchor::Center, 40.0, theme.tile_text);
+        }
+        self.particles.draw();
+        layout::set_offset(base.0, base.1);
+    }
 }
 
-pub unsafe fn draw_screen() {
-    clear_background(DARKGRAY);
-    
-    draw_hud();
-    
-    draw_tiles();
-    draw_rectangle(
-        screen_width() / 2.0 + 50.0,
-        screen_height() - 225.0,
-        475.0,
-        50.0,
-        WHITE
-    );
-
-    let letter_dim = measure_text(&game_state.current_word, None, 40, 1.0);
-    draw_text(
-        &game_state.current_word,
-        screen_width() / 2.0 + 50.0 + 475.0 / 2.0 - letter_dim.width / 2.0,
-        screen_height() - 225.0 + 35.0,
-        40.0,
-        BLACK
-    );
-    
-    let play_button = ui::button::Button::new(
-        screen_width() / 2.0 + 200.0,
-        screen_height() - 150.0,
-        475.0 / 3.0,
-        50.0,
-        GREEN,
-        "Play Word".to_string(),
-        35.0
-    );
-    play_button.draw();
-
-    if game_state.current_word.len() != 0 && (play_button.is_clicked() || is_key_pressed(KeyCode::Enter)) {
-        let score = score_word().unwrap_or(0);
-        clear_word();
-        game_state.total_score += score;
-    }
-
-    let x_button = ui::button::Button::new(
-        screen_width() - 165.0,
-        screen_height() - 150.0,
-        50.0,
-        50.0,
-        RED,
-        "X".to_string(),
-        40.0
-    );
-    x_button.draw();
-    if x_button.is_clicked() || is_key_pressed(KeyCode::Backspace) {
-        clear_word();
-    }
-    
-    let shuffle_button = ui::button::Button::new(
-        screen_width() / 2.0 + 50.0 + 60.0,
-        screen_height() - 75.0,
-        475.0 / 3.0,
-        50.0,
-        YELLOW,
-        "Shuffle".to_string(),
-        40.0
-    );
-    shuffle_button.draw();
-    if shuffle_button.is_clicked() {
-        shuffle_tiles();
-    }
-    
-    let discard_button = ui::button::Button::new(
-        screen_width() / 2.0 + 50.0 + 235.0,
-        screen_height() - 75.0,
-        475.0 / 3.0,
-        50.0,
-        RED,
-        "Discard".to_string(),
-        40.0
-    );
-    discard_button.draw();
-    if discard_button.is_clicked() {
-        discard_tiles();
-    }
-
-    update();
+fn draw_hud(run: &Run, game_layout: &GameLayout, shown_score: i32) {
+    let theme = theme::current();
+    let radius = theme.corner_radius;
+    let hud = game_layout.hud;
+    theme::draw_rounded_rect(hud.x, hud.y, hud.w, hud.h, radius, theme.panel);
+
+    let target = game_layout.target;
+    let target_x = target.center().0;
+    theme::draw_rounded_rect(target.x, target.y, target.w, target.h, radius, theme.target_panel);
+    layout::draw_centered_text(tr("game.score_at_least"), target_x, target.y + 65.0, 55.0, theme.target_text);
+    layout::draw_centered_text(&run.round_score.to_string(), target_x, target.y + 125.0, 60.0,
+                               theme.target_text);
+
+    let round_score = game_layout.round_score;
+    let round_score_x = round_score.center().0;
+    theme::draw_rounded_rect(round_score.x, round_score.y, round_score.w, round_score.h, radius, theme.accent);
+    layout::draw_centered_text(tr("game.round_score"), round_score_x, round_score.y + 80.0, 50.0, theme.panel_text);
+    layout::draw_centered_text(&shown_score.to_string(), round_score_x,
+                               round_score.y + 165.0, 120.0, theme.panel_text);
+
+    let counters = [
+        (game_layout.words, theme.words_panel, theme.panel_text, tr("game.words"), run.words_remaining),
+        (game_layout.discards, theme.discards_panel, theme.panel_text, tr("game.discards"), run.discards),
+        (game_layout.round, theme.target_panel, theme.target_text, tr("game.round"), run.round),
+    ];
+    for (rect, background, text_color, label, value) in counters {
+        let center_x = rect.center().0;
+        theme::draw_rounded_rect(rect.x, rect.y, rect.w, rect.h, radius, background);
+        layout::draw_centered_text(label, center_x, rect.y + 25.0, 30.0, text_color);
+        layout::draw_centered_text(&value.to_string(), center_x, rect.y + 60.0, 50.0, text_color);
+    }
+
+    let run_info = tr_args("game.run_info", &[
+        ("mode", &run.mode.name()),
+        ("seed", &run.seed),
+        ("score", &run.run_score),
+    ]);
+    theme::draw_text(&run_info, hud.x, layout::height() - 30.0, 25.0, theme.text);
+
+    if let Some(time_left) = run.time_left {
+        let time = tr_args("game.time", &[("seconds", &(time_left.ceil() as i32))]);
+        let board = game_layout.tiles[0];
+        theme::draw_text(&time, board.x, board.y - 20.0, 40.0,
+                  if time_left < 10.0 { theme.danger } else { theme.text });
+    }
 }
 
-unsafe fn draw_hud() {
-    draw_rectangle(
-        100.0,
-        50.0,
-        450.0,
-        600.0,
-        WHITE
-    );
-    
-    draw_rectangle(
-        125.0,
-        75.0,
-        400.0,
-        150.0,
-        BLACK
-    );
-    draw_text(
-        "Score at least",
-        125.0 + 300.0 / 2.0
-            - measure_text("Score at least", None, 40, 1.0).width / 2.0,
-        190.0 - 50.0,
-        55.0,
-        WHITE
-    );
-    draw_text(
-        &game_state.round_score.to_string(),
-        125.0 + 190.0
-            - measure_text(&game_state.round_score.to_string(), None, 40, 1.0).width / 2.0,
-        250.0 - 50.0,
-        60.0,
-        WHITE
-    );
-    
-    draw_rectangle(
-        125.0,
-        250.0,
-        400.0,
-        200.0,
-        LIME
-    );
-    draw_text(
-        "Round Score",
-        125.0 + 400.0 / 2.0
-            - measure_text("Round Score", None, 50, 1.0).width / 2.0,
-        250.0 + 90.0
-            - measure_text("Round Score", None, 50, 1.0).height / 2.0,
-        50.0,
-        BLACK
-    );
-    draw_text(
-        &game_state.total_score.to_string(),
-        125.0 + 400.0 / 2.0
-            - measure_text(&game_state.total_score.to_string(), None, 120, 1.0).width / 2.0,
-        250.0 + 190.0
-            - measure_text(&game_state.total_score.to_string(), None, 120, 1.0).height / 2.0,
-        120.0,
-        BLACK
-    );
-
-    let coral_rgba = Color::new(1.0, 127.0 / 255.0, 80.0 / 255.0, 1.0);
-    
-    draw_rectangle(
-        175.0,
-        460.0,
-        125.0,
-        75.0,
-        SKYBLUE
-    );
-    draw_text(
-        "Words",
-        175.0 + 125.0 / 2.0 - measure_text("Words", None, 30, 1.0).width / 2.0,
-        460.0 + 25.0,
-        30.0,
-        BLACK
-    );
-    draw_text(
-        &game_state.words_remaining.to_string(),
-        175.0 + 125.0 / 2.0 - measure_text(&game_state.words_remaining.to_string(),
-                                           None, 50, 1.0).width / 2.0,
-        460.0 + 60.0,
-        50.0,
-        BLACK
-    );
-
-    draw_rectangle(
-        350.0,
-        460.0,
-        125.0,
-        75.0,
-        coral_rgba
-    );
-    draw_text(
-        "Discards",
-        350.0 + 125.0 / 2.0 - measure_text("Discards", None, 30, 1.0).width / 2.0,
-        460.0 + 25.0,
-        30.0,
-        BLACK
-    );
-
-    let d = &game_state.discards.to_string();
-    draw_text(
-        &d,
-        350.0 + 125.0 / 2.0 - measure_text(&d, None, 50, 1.0).width / 2.0,
-        460.0 + 60.0,
-        50.0,
-        BLACK
-    );
-
-    draw_rectangle(
-        350.0 - 90.0,
-        560.0,
-        125.0,
-        75.0,
-        BLACK
-    );
-    draw_text(
-        "Round",
-        350.0 - 90.0 + 125.0 / 2.0 - measure_text("Round", None, 30, 1.0).width / 2.0,
-        560.0 + 25.0,
-        30.0,
-        WHITE
-    );
-    draw_text(
-        &game_state.round.to_string(),
-        350.0 - 90.0 + 125.0 / 2.0 - measure_text(&game_state.round.to_string(), None, 50, 1.0).width / 2.0,
-        560.0 + 60.0,
-        50.0,
-        WHITE
-    );
+/// Where the first letter of the word starts inside the word box.
+fn word_start_x(game_state: &GameState, word_box: Rect) -> f32 {
+    word_box.center().0 - game_state.selected.len() as f32 * LETTER_SLOT_WIDTH / 2.0
 }
 
-unsafe fn draw_tiles() {
-    let tiles = &mut game_state.tiles;
-    if tiles.is_empty() {
-        let mut start_y = 100.0;
-        let mut rand_indexes: Vec<usize> = Vec::new();
-        let mut rng = ::rand::thread_rng();
-
-        while rand_indexes.len() < 12 {
-            let rand_index = rng.gen_range(0..game_state.words.len());
-            if !rand_indexes.contains(&rand_index) {
-                rand_indexes.push(rand_index);
+/// Where a tile dropped at `x` goes in the word: before the letter under
+/// it, or after it when dropped on its right half.
+fn drop_position(game_state: &GameState, word_box: Rect, x: f32) -> usize {
+    let start_x = word_start_x(game_state, word_box);
+    let slot = ((x - start_x) / LETTER_SLOT_WIDTH + 0.5).floor();
+    (slot.max(0.0) as usize).min(game_state.selected.len())
+}
+
+/// Tapping a letter in the word box puts its tile back on the rack.
+/// Dragging a letter moves it to another position in the word, or back to
+/// the rack when it's dropped outside the word box.
+fn word_box_input(game_state: &mut GameState, word_box: Rect) {
+    let count = game_state.selected.len();
+    let start_x = word_start_x(game_state, word_box);
+    let pointer = pointer::position();
+    let in_box = pointer.1 >= word_box.y && pointer.1 <= word_box.y + word_box.h;
+    let slot = ((pointer.0 - start_x) / LETTER_SLOT_WIDTH).floor();
+
+    if pointer::is_pressed() && in_box && slot >= 0.0 && (slot as usize) < count {
+        game_state.word_drag = Some(Drag::new(slot as usize, pointer));
+    }
+
+    if let Some(drag) = game_state.word_drag.as_mut() {
+        if pointer::is_down() {
+            drag.track(pointer);
+        } else {
+            let from = drag.from;
+            let dragging = drag.dragging;
+            game_state.word_drag = None;
+            // Backspace may have taken the letter away mid-drag.
+            if from >= count {
+                return;
+            }
+            if dragging && word_box.contains(pointer) {
+                let to = (slot.max(0.0) as usize).min(count - 1);
+                move_letter(game_state, from, to);
+            } else {
+                remove_letter(game_state, from);
             }
         }
+    }
+}
+
+/// Tapping a tile adds its letter to the end of the word. Dragging it into
+/// the word box puts the letter where it's dropped, and dropping it anywhere
+/// else sends it back. Returns the tile dropped into the word box, which is
+/// already in place and doesn't need to fly there.
+fn rack_input(game_state: &mut GameState, game_layout: &GameLayout) -> Option<usize> {
+    let pointer = pointer::position();
+    if pointer::is_pressed() && game_state.word_drag.is_none() {
+        let pressed = game_state.tiles.iter()
+            .position(|tile| !tile.is_used() && pointer::is_over(tile.rect()));
+        if let Some(tile_index) = pressed {
+            game_state.tile_drag = Some(Drag::new(tile_index, pointer));
+        }
+    }
 
-        let mut inc = 0;
-        for _ in 0..3 {
-            let mut start_x = screen_width() / 2.0 + 50.0;
-            for c in 0..4 {
-                let tile = ui::tile::Tile::new(*game_state.words.get(rand_indexes[inc * 4 + c]).unwrap(),
-                                               start_x, start_y);
-                tiles.push(tile);
-                start_x += 125.0;
-            }
-            start_y += 125.0;
-            inc += 1;
+    let drag = game_state.tile_drag.as_mut()?;
+    let home = game_layout.tiles[drag.from];
+    if pointer::is_down() {
+        drag.track(pointer);
+        if drag.dragging {
+            let moved = home.offset(pointer.0 - drag.start.0, pointer.1 - drag.start.1);
+            game_state.tiles[drag.from].place(moved);
         }
+        return None;
     }
-    
-    for tile in &game_state.tiles {
-        tile.draw();
+
+    let drag = game_state.tile_drag.take()?;
+    if !drag.dragging {
+        select_tile(game_state, drag.from);
+        return None;
     }
+    game_state.tiles[drag.from].slide_to(home, 0.0);
+    if !game_layout.word_box.contains(pointer) {
+        return None;
+    }
+    let position = drop_position(game_state, game_layout.word_box, pointer.0);
+    insert_tile(game_state, drag.from, position);
+    Some(drag.from)
 }
 
-fn redraw(guard: &mut Vec<ui::tile::Tile>) {
-    let mut start_y = 100.0;
-    let mut inc = 0;
-    for r in 0..3 {
-        let mut start_x = screen_width() / 2.0 + 50.0;
-        for c in 0..4 {
-            let tile = &mut guard[inc * 4 + c];
-            tile.get_button().set_x(start_x);
-            tile.get_button().set_y(start_y);
-            start_x += 125.0;
+/// Draws the word being built, with a dragged letter following the mouse.
+/// Letters of the `flying` tiles are left out until they land.
+fn draw_word_box(game_state: &GameState, word_box: Rect, flying: &[usize]) {
+    let theme = theme::current();
+    theme::draw_rounded_rect(word_box.x, word_box.y, word_box.w, word_box.h, theme.corner_radius, theme.panel);
+
+    let start_x = word_start_x(game_state, word_box);
+    let pointer = pointer::position();
+    let dragged = game_state.word_drag.as_ref().filter(|d| d.dragging).map(|d| d.from);
+
+    // A caret shows where a tile dragged over the box will go.
+    let dragging_tile = game_state.tile_drag.as_ref().is_some_and(|d| d.dragging);
+    if dragging_tile && word_box.contains(pointer) {
+        let caret_x = start_x + drop_position(game_state, word_box, pointer.0) as f32 * LETTER_SLOT_WIDTH;
+        draw_line(caret_x, word_box.y + 8.0, caret_x, word_box.y + word_box.h - 8.0, 3.0, theme.focus);
+    }
+
+    for (position, &tile_index) in game_state.selected.iter().enumerate() {
+        if flying.contains(&tile_index) {
+            continue;
+        }
+        let letter = game_state.tiles[tile_index].get_letter().to_string();
+        let letter_dim = theme::measure_text(&letter, 40);
+        if dragged == Some(position) {
+            theme::draw_text(&letter, pointer.0 - letter_dim.width / 2.0, pointer.1 + 15.0, 40.0, theme.highlight);
+        } else {
+            let slot_x = start_x + position as f32 * LETTER_SLOT_WIDTH;
+            theme::draw_text(
+                &letter,
+                slot_x + LETTER_SLOT_WIDTH / 2.0 - letter_dim.width / 2.0,
+                word_box.y + 35.0,
+                40.0,
+                theme.panel_text
+            );
         }
-        start_y += 125.0;
-        inc += 1;
     }
 }
 
-unsafe fn shuffle_tiles() {
-    let mut tiles = &mut game_state.tiles;
-    for i in 0..tiles.len() {
-        let j = rand::gen_range(0, tiles.len());
+/// Lays out a new rack once the run has dealt one.
+fn deal_tiles(game_state: &mut GameState, game_layout: &GameLayout) {
+    if game_state.run.deal() {
+        // The new rack slides up from below the screen, one tile after another.
+        let color = theme::skin_color(game_state.run.skin);
+        game_state.tiles = game_state.run.rack.iter().zip(&game_layout.tiles).enumerate()
+            .map(|(i, (&letter, rect))| {
+                let start = Rect::new(rect.x, layout::height() + 20.0, rect.w, rect.h);
+                let mut tile = ui::tile::Tile::new(letter, start, color);
+                tile.slide_to(*rect, i as f32 * 0.03);
+                tile
+            })
+            .collect();
+        game_state.record();
+    }
+}
+
+fn redraw(guard: &mut [ui::tile::Tile], game_layout: &GameLayout) {
+    for (tile, rect) in guard.iter_mut().zip(&game_layout.tiles) {
+        tile.slide_to(*rect, 0.0);
+    }
+}
+
+/// Drops any drag, as the tile or letter being dragged moves.
+fn shuffle_tiles(game_state: &mut GameState, game_layout: &GameLayout) {
+    game_state.word_drag = None;
+    game_state.tile_drag = None;
+    let tiles = &mut game_state.tiles;
+    for (i, j) in game_state.run.shuffle() {
         tiles.swap(i, j);
+        for selected in game_state.selected.iter_mut() {
+            if *selected == i {
+                *selected = j;
+            } else if *selected == j {
+                *selected = i;
+            }
+        }
     }
-    redraw(tiles);
+    redraw(tiles, game_layout);
+    game_state.record();
 }
 
-unsafe fn discard_tiles() {
-    if game_state.discards > 0 {
-        game_state.tiles.clear();
-        game_state.discards -= 1;
-        clear_word();
+/// Throws the rack away for a new one. Returns the old tiles so the screen
+/// can animate them out.
+fn discard_tiles(game_state: &mut GameState) -> Vec<ui::tile::Tile> {
+    if !game_state.run.discard() {
+        return Vec::new();
     }
+    game_state.record();
+    clear_word(game_state);
+    std::mem::take(&mut game_state.tiles)
 }
 
-unsafe fn clear_word() {
-    &game_state.current_word.clear();
+fn select_tile(game_state: &mut GameState, tile_index: usize) {
+    insert_tile(game_state, tile_index, game_state.selected.len());
 }
 
-unsafe fn score_word() -> Option<i32> {
-    if game_state.words_remaining > 0 {
-        let word = &game_state.current_word;
-        let set: HashSet<_> = word.chars().collect();
-        if game_state.words_db.contains(word) && !game_state.guessed_words.contains(word)
-            && set.len() != 1 {
-            let mut score = 0;
-            for char in word.chars() {
-                score += game_state.scoring.get(&char).unwrap_or(&0);
-            }
-            score *= word.len() as i32;
-            game_state.guessed_words.insert(word.clone());
-            game_state.words_remaining -= 1;
-            return Some(score);
-        }
-        game_state.words_remaining -= 1;
+/// Puts the tile's letter into the word at `position`, unless it's already
+/// in the word.
+fn insert_tile(game_state: &mut GameState, tile_index: usize, position: usize) {
+    if game_state.tiles[tile_index].is_used() {
+        return;
     }
-    None
+    audio::play(Sound::TileClick);
+    game_state.tiles[tile_index].set_used(true);
+    game_state.selected.insert(position, tile_index);
+    game_state.sync_word();
 }
 
-unsafe fn update() {
-    for tile in &mut game_state.tiles {
-        let tile_letter = tile.get_letter();
-        if let Some(key_code) = char_to_key_code(tile_letter) {
-            if is_key_pressed(key_code) || tile.is_clicked() {
-                game_state.current_word.push(tile_letter);
-            }
-        }
+/// Takes the letter at `position` out of the word and returns its tile to the rack.
+/// A letter being dragged keeps following the pointer, unless it's the one
+/// taken out.
+fn remove_letter(game_state: &mut GameState, position: usize) {
+    match game_state.word_drag.as_mut() {
+        Some(drag) if drag.from == position => game_state.word_drag = None,
+        Some(drag) if drag.from > position => drag.from -= 1,
+        _ => {}
     }
+    let tile_index = game_state.selected.remove(position);
+    game_state.tiles[tile_index].set_used(false);
+    game_state.sync_word();
+}
 
-    if game_state.total_score >= game_state.round_score as i32 {
-        ScreenManager::switch_screen(Screen::RoundWinScreen);
-        game_state.round_score += 100;
-        discard_tiles();
-        game_state.discards = 3;
-        game_state.words_remaining = 4;
-        if game_state.round == 5 {
-            ScreenManager::switch_screen(Screen::WinScreen);
-        } else {
-            game_state.round += 1;
+fn move_letter(game_state: &mut GameState, from: usize, to: usize) {
+    let tile_index = game_state.selected.remove(from);
+    game_state.selected.insert(to, tile_index);
+    game_state.sync_word();
+}
+
+fn clear_word(game_state: &mut GameState) {
+    for tile_index in game_state.selected.drain(..) {
+        if let Some(tile) = game_state.tiles.get_mut(tile_index) {
+            tile.set_used(false);
         }
-        game_state.guessed_words = HashSet::new();
-        game_state.total_score = 0;
     }
+    game_state.word_drag = None;
+    game_state.tile_drag = None;
+    game_state.current_word.clear();
+}
 
-    if game_state.words_remaining == 0 {
-        ScreenManager::switch_screen(Screen::LoseScreen);
-        game_state.words_remaining = 4;
-        discard_tiles();
-        game_state.discards = 3;
-        game_state.total_score = 0;
-        game_state.round_score = 750;
-        game_state.round = 1;
+fn show_hint(game_state: &mut GameState) {
+    match game_state.run.hint() {
+        Hint::Word(word) => {
+            let first = i18n::display_letter(word.chars().next().unwrap_or(' '));
+            toast::show(tr_args("game.hint_text", &[("length", &word.chars().count()), ("letter", &first)]));
+        }
+        Hint::NoHintsLeft => toast::show(tr("game.no_hints").to_string()),
+        Hint::NoWords => toast::show(tr("game.no_words").to_string()),
     }
+    game_state.record();
+}
+
+/// Cheats for tuning, from the developer console. Every one that works
+/// marks the run as cheated.
+#[cfg(feature = "dev")]
+fn dev_command(game_state: &mut GameState, game_layout: &GameLayout, args: &[&str])
+               -> Option<Result<String, String>> {
+    use crate::rules::RACK_SIZE;
+    use crate::unlocks::Modifier;
+
+    let run = &mut game_state.run;
+    let number = |value: Option<&&str>| value.and_then(|value| value.parse::<i64>().ok());
+    let result = match args {
+        ["setscore", ..] => match number(args.get(1)) {
+            Some(score) => {
+                run.total_score = score as i32;
+                Ok(format!("Score set to {}", score))
+            }
+            None => Err("Usage: setscore <n>".to_string()),
+        },
+        ["round", ..] => match number(args.get(1)).filter(|&round| round >= 1) {
+            Some(round) => {
+                // The target moves with the round, as if the rounds between had been won.
+                let steps = round - run.round as i64;
+                run.round_score = (run.round_score as i64 + steps * run.ruleset.target_step as i64).max(0) as u64;
+                run.round = round as u32;
+                Ok(format!("Round {}, target {}", run.round, run.round_score))
+            }
+            None => Err("Usage: round <n>".to_string()),
+        },
+        ["rack", letters] => {
+            let letters: Vec<char> = letters.chars().flat_map(char::to_lowercase).collect();
+            if letters.is_empty() || letters.len() > RACK_SIZE {
+                Err(format!("A rack has 1 to {} letters", RACK_SIZE))
+            } else {
+                run.rack = letters;
+                clear_word(game_state);
+                game_state.place_tiles(game_layout);
+                Ok(format!("Rack set to {}", game_state.run.rack.iter().collect::<String>()))
+            }
+        }
+        ["give", name] => {
+            let wanted = name.replace(['-', '_'], "");
+            match Modifier::ALL.into_iter().find(|modifier| format!("{:?}", modifier).eq_ignore_ascii_case(&wanted)) {
+                Some(modifier) if run.modifiers.contains(&modifier) => Err(format!("Already have {:?}", modifier)),
+                Some(modifier) => {
+                    if modifier == Modifier::ExtraDiscard {
+                        run.discards_per_round += 1;
+                        run.discards += 1;
+                    }
+                    run.modifiers.push(modifier);
+                    Ok(format!("Gave {:?}", modifier))
+                }
+                None => Err(format!("No modifier {}. Try long-words, rare-bonus or extra-discard", name)),
+            }
+        }
+        ["win"] => {
+            run.total_score = run.total_score.max(run.round_score as i32);
+            Ok("Round won".to_string())
+        }
+        _ => return None,
+    };
+    Some(result)
 }
-fn char_to_key_code(c: char) -> Option<KeyCode> {
-    match c {
-        'a' => Some(KeyCode::A), 'b' => Some(KeyCode::B), 'c' => Some(KeyCode::C),
-        'd' => Some(KeyCode::D), 'e' => Some(KeyCode::E), 'f' => Some(KeyCode::F),
-        'g' => Some(KeyCode::G), 'h' => Some(KeyCode::H), 'i' => Some(KeyCode::I),
-        'j' => Some(KeyCode::J), 'k' => Some(KeyCode::K), 'l' => Some(KeyCode::L),
-        'm' => Some(KeyCode::M), 'n' => Some(KeyCode::N), 'o' => Some(KeyCode::O),
-        'p' => Some(KeyCode::P), 'q' => Some(KeyCode::Q), 'r' => Some(KeyCode::R),
-        's' => Some(KeyCode::S), 't' => Some(KeyCode::T), 'u' => Some(KeyCode::U),
-        'v' => Some(KeyCode::V), 'w' => Some(KeyCode::W), 'x' => Some(KeyCode::X),
-        'y' => Some(KeyCode::Y), 'z' => Some(KeyCode::Z), _ => None,
+
+/// Ticks the blitz timer and checks whether the round or the run is over.
+fn update(game_state: &mut GameState) -> Transition {
+    game_state.run.tick(get_frame_time());
+
+    match game_state.run.check() {
+        Outcome::Playing => Transition::None,
+        Outcome::RoundWon => {
+            game_state.tiles.clear();
+            clear_word(game_state);
+            Transition::Push(Box::new(RoundWinScreen::new()))
+        }
+        Outcome::RunWon => {
+            game_state.tiles.clear();
+            clear_word(game_state);
+            Transition::Replace(Box::new(WinScreen::new(game_state.finish_run())))
+        }
+        Outcome::RunLost => Transition::Replace(Box::new(LoseScreen::new(game_state.finish_run()))),
     }
 }
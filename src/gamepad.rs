@@ -0,0 +1,143 @@
+use std::cell::RefCell;
+
+/// Controller buttons the game reacts to, named after their position so the
+/// same layout works for Xbox, PlayStation and Steam Deck pads.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PadButton {
+    South,
+    East,
+    West,
+    North,
+    LeftShoulder,
+    RightShoulder,
+    Start,
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+thread_local! {
+    static PRESSED: RefCell<Vec<PadButton>> = const { RefCell::new(Vec::new()) };
+}
+
+pub fn is_pressed(button: PadButton) -> bool {
+    PRESSED.with(|pressed| pressed.borrow().contains(&button))
+}
+
+/// Collects this frame's button presses. Called once per frame before any
+/// screen reads input.
+pub fn update() {
+    let pressed = backend::poll();
+    PRESSED.with(|current| *current.borrow_mut() = pressed);
+}
+
+#[cfg(feature = "gamepad")]
+mod backend {
+    use std::cell::RefCell;
+
+    use gilrs::{Axis, Button, EventType, Gilrs};
+
+    use super::PadButton;
+
+    /// How far a stick has to be pushed to count as a d-pad press.
+    const STICK_THRESHOLD: f32 = 0.6;
+
+    struct Backend {
+        gilrs: Option<Gilrs>,
+        stick: (i8, i8),
+    }
+
+    thread_local! {
+        static BACKEND: RefCell<Backend> = RefCell::new(Backend {
+            gilrs: match Gilrs::new() {
+                Ok(gilrs) => Some(gilrs),
+                Err(e) => {
+                    tracing::warn!("Failed to start gamepad support: {}", e);
+                    None
+                }
+            },
+            stick: (0, 0),
+        });
+    }
+
+    fn map_button(button: Button) -> Option<PadButton> {
+        match button {
+            Button::South => Some(PadButton::South),
+            Button::East => Some(PadButton::East),
+            Button::West => Some(PadButton::West),
+            Button::North => Some(PadButton::North),
+            Button::LeftTrigger => Some(PadButton::LeftShoulder),
+            Button::RightTrigger => Some(PadButton::RightShoulder),
+            Button::Start => Some(PadButton::Start),
+            Button::DPadUp => Some(PadButton::Up),
+            Button::DPadDown => Some(PadButton::Down),
+            Button::DPadLeft => Some(PadButton::Left),
+            Button::DPadRight => Some(PadButton::Right),
+            _ => None,
+        }
+    }
+
+    fn stick_direction(value: f32) -> i8 {
+        if value > STICK_THRESHOLD {
+            1
+        } else if value < -STICK_THRESHOLD {
+            -1
+        } else {
+            0
+        }
+    }
+
+    pub fn poll() -> Vec<PadButton> {
+        BACKEND.with(|backend| {
+            let backend = &mut *backend.borrow_mut();
+            let mut pressed = Vec::new();
+            let Some(gilrs) = backend.gilrs.as_mut() else {
+                return pressed;
+            };
+
+            while let Some(event) = gilrs.next_event() {
+                match event.event {
+                    EventType::ButtonPressed(button, _) => {
+                        pressed.extend(map_button(button));
+                    }
+                    // The left stick moves the cursor like the d-pad, one
+                    // step each time it is pushed past the threshold.
+                    EventType::AxisChanged(Axis::LeftStickX, value, _) => {
+                        let direction = stick_direction(value);
+                        if direction != backend.stick.0 {
+                            match direction {
+                                1 => pressed.push(PadButton::Right),
+                                -1 => pressed.push(PadButton::Left),
+                                _ => {}
+                            }
+                        }
+                        backend.stick.0 = direction;
+                    }
+                    EventType::AxisChanged(Axis::LeftStickY, value, _) => {
+                        let direction = stick_direction(value);
+                        if direction != backend.stick.1 {
+                            match direction {
+                                1 => pressed.push(PadButton::Up),
+                                -1 => pressed.push(PadButton::Down),
+                                _ => {}
+                            }
+                        }
+                        backend.stick.1 = direction;
+                    }
+                    _ => {}
+                }
+            }
+            pressed
+        })
+    }
+}
+
+#[cfg(not(feature = "gamepad"))]
+mod backend {
+    use super::PadButton;
+
+    pub fn poll() -> Vec<PadButton> {
+        Vec::new()
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+
+/// The language used when the settings name one that doesn't exist.
+pub const DEFAULT_LANGUAGE: &str = "English";
+
+const BUILT_IN: [&str; 3] = [
+    include_str!("languages/english.json"),
+    include_str!("languages/spanish.json"),
+    include_str!("languages/german.json"),
+];
+
+/// One letter of a language's tile set.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct Letter {
+    pub letter: char,
+    pub score: i32,
+    /// How often the letter is dealt compared to the others.
+    pub weight: u32,
+    /// Rare letters earn the Rare Bonus modifier and the Rare Find achievement.
+    #[serde(default)]
+    pub rare: bool,
+}
+
+/// Everything that changes with the player's language: the UI text, the
+/// tiles and the dictionary that words are checked against.
+#[derive(Debug, Deserialize)]
+pub struct Language {
+    pub name: String,
+    /// A JSON object whose keys are the words of the language.
+    pub dictionary: String,
+    /// Where the parsed dictionary is cached between launches.
+    pub cache: String,
+    pub letters: Vec<Letter>,
+    strings: HashMap<String, String>,
+}
+
+impl Language {
+    pub fn letter(&self, c: char) -> Option<&Letter> {
+        self.letters.iter().find(|letter| letter.letter == c)
+    }
+
+    pub fn is_rare(&self, c: char) -> bool {
+        self.letter(c).is_some_and(|letter| letter.rare)
+    }
+
+    /// The rare letters as they appear on screen, e.g. "Q, Z, X, J".
+    pub fn rare_letters(&self) -> String {
+        self.letters.iter()
+            .filter(|letter| letter.rare)
+            .map(|letter| display_letter(letter.letter))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+static LANGUAGES: Lazy<Vec<Language>> = Lazy::new(|| {
+    BUILT_IN.iter()
+        .map(|json| serde_json::from_str(json).expect("Built-in languages are valid"))
+        .collect()
+});
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+
+/// Switches the UI text and, from the next run on, the tiles and dictionary.
+/// Unknown names fall back to English.
+pub fn apply(name: &str) {
+    let index = LANGUAGES.iter().position(|language| language.name == name).unwrap_or(0);
+    CURRENT.store(index, Ordering::Relaxed);
+}
+
+pub fn current() -> &'static Language {
+    &LANGUAGES[CURRENT.load(Ordering::Relaxed)]
+}
+
+pub fn language(name: &str) -> Option<&'static Language> {
+    LANGUAGES.iter().find(|language| language.name == name)
+}
+
+/// Like `language`, but ignoring case, for names typed by hand.
+pub fn find(name: &str) -> Option<&'static Language> {
+    let name = name.to_lowercase();
+    LANGUAGES.iter().find(|language| language.name.to_lowercase() == name)
+}
+
+pub fn available() -> Vec<String> {
+    LANGUAGES.iter().map(|language| language.name.clone()).collect()
+}
+
+/// The text for `key` in the current language. Keys a translation is
+/// missing fall back to English, and unknown keys show up as themselves so
+/// they're easy to spot.
+pub fn tr(key: &str) -> &str {
+    current().strings.get(key)
+        .or_else(|| LANGUAGES[0].strings.get(key))
+        .map_or(key, String::as_str)
+}
+
+/// Like `tr`, with every `{name}` in the text replaced by its value.
+pub fn tr_args(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut text = tr(key).to_string();
+    for (name, value) in args {
+        text = text.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    text
+}
+
+/// Letters are shown in capitals where a language has a single capital
+/// for them; ß stays as it is rather than turning into "SS".
+pub fn display_letter(c: char) -> String {
+    let mut upper = c.to_uppercase();
+    match (upper.next(), upper.next()) {
+        (Some(upper), None) => upper.to_string(),
+        _ => c.to_string(),
+    }
+}
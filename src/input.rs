@@ -0,0 +1,274 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+use macroquad::prelude::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::gamepad::{self, PadButton};
+use crate::i18n::{self, tr};
+use crate::settings;
+
+/// Something the player can do in a game with a single key press.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Action {
+    Submit,
+    RemoveLetter,
+    ClearWord,
+    Shuffle,
+    Discard,
+    Hint,
+    CursorUp,
+    CursorDown,
+    CursorLeft,
+    CursorRight,
+    SelectTile,
+    Pause,
+}
+
+impl Action {
+    pub const ALL: [Action; 12] = [
+        Action::Submit,
+        Action::RemoveLetter,
+        Action::ClearWord,
+        Action::Shuffle,
+        Action::Discard,
+        Action::Hint,
+        Action::CursorUp,
+        Action::CursorDown,
+        Action::CursorLeft,
+        Action::CursorRight,
+        Action::SelectTile,
+        Action::Pause,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Submit => tr("action.submit"),
+            Action::RemoveLetter => tr("action.remove_letter"),
+            Action::ClearWord => tr("action.clear_word"),
+            Action::Shuffle => tr("action.shuffle"),
+            Action::Discard => tr("action.discard"),
+            Action::Hint => tr("action.hint"),
+            Action::CursorUp => tr("action.cursor_up"),
+            Action::CursorDown => tr("action.cursor_down"),
+            Action::CursorLeft => tr("action.cursor_left"),
+            Action::CursorRight => tr("action.cursor_right"),
+            Action::SelectTile => tr("action.select_tile"),
+            Action::Pause => tr("action.pause"),
+        }
+    }
+
+    fn default_key(&self) -> KeyCode {
+        match self {
+            Action::Submit => KeyCode::Enter,
+            Action::RemoveLetter => KeyCode::Backspace,
+            Action::ClearWord => KeyCode::Delete,
+            Action::Shuffle => KeyCode::Key1,
+            Action::Discard => KeyCode::Key2,
+            Action::Hint => KeyCode::Key3,
+            Action::CursorUp => KeyCode::Up,
+            Action::CursorDown => KeyCode::Down,
+            Action::CursorLeft => KeyCode::Left,
+            Action::CursorRight => KeyCode::Right,
+            Action::SelectTile => KeyCode::Space,
+            Action::Pause => KeyCode::Escape,
+        }
+    }
+
+    /// Controller buttons are fixed: face buttons pick, play and clear,
+    /// shoulders shuffle and discard.
+    fn pad_button(&self) -> Option<PadButton> {
+        match self {
+            Action::Submit => Some(PadButton::West),
+            Action::RemoveLetter => None,
+            Action::ClearWord => Some(PadButton::East),
+            Action::Shuffle => Some(PadButton::LeftShoulder),
+            Action::Discard => Some(PadButton::RightShoulder),
+            Action::Hint => Some(PadButton::North),
+            Action::CursorUp => Some(PadButton::Up),
+            Action::CursorDown => Some(PadButton::Down),
+            Action::CursorLeft => Some(PadButton::Left),
+            Action::CursorRight => Some(PadButton::Right),
+            Action::SelectTile => Some(PadButton::South),
+            Action::Pause => Some(PadButton::Start),
+        }
+    }
+}
+
+/// Which key triggers each action. Saved in the settings file by key name.
+#[derive(Clone, Debug)]
+pub struct KeyBindings {
+    keys: BTreeMap<Action, KeyCode>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        KeyBindings {
+            keys: Action::ALL.iter().map(|a| (*a, a.default_key())).collect(),
+        }
+    }
+}
+
+impl KeyBindings {
+    pub fn key(&self, action: Action) -> KeyCode {
+        self.keys.get(&action).copied().unwrap_or_else(|| action.default_key())
+    }
+
+    /// Binds `key` to `action`. An action that already used `key` takes over
+    /// `action`'s old key, so no key ever triggers two actions.
+    pub fn bind(&mut self, action: Action, key: KeyCode) {
+        let old_key = self.key(action);
+        for (other, other_key) in self.keys.iter_mut() {
+            if *other != action && *other_key == key {
+                *other_key = old_key;
+            }
+        }
+        self.keys.insert(action, key);
+    }
+}
+
+impl Serialize for KeyBindings {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let names: BTreeMap<Action, String> = self.keys.iter()
+            .map(|(action, key)| (*action, key_name(*key)))
+            .collect();
+        names.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyBindings {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let names: BTreeMap<Action, String> = BTreeMap::deserialize(deserializer)?;
+        let mut bindings = KeyBindings::default();
+        for (action, name) in names {
+            if let Some(key) = key_from_name(&name) {
+                bindings.bind(action, key);
+            }
+        }
+        Ok(bindings)
+    }
+}
+
+/// Keys that can be bound to actions. Letters are left out since typing them
+/// always builds words, and so are the keys that type letters on Spanish and
+/// German keyboards: ñ, ö, ä, ü and ß.
+const NAMED_KEYS: [KeyCode; 55] = [
+    KeyCode::Key0, KeyCode::Key1, KeyCode::Key2, KeyCode::Key3, KeyCode::Key4,
+    KeyCode::Key5, KeyCode::Key6, KeyCode::Key7, KeyCode::Key8, KeyCode::Key9,
+    KeyCode::F1, KeyCode::F2, KeyCode::F3, KeyCode::F4, KeyCode::F5, KeyCode::F6,
+    KeyCode::F7, KeyCode::F8, KeyCode::F9, KeyCode::F10, KeyCode::F11, KeyCode::F12,
+    KeyCode::Space, KeyCode::Enter, KeyCode::Tab, KeyCode::Escape, KeyCode::Backspace,
+    KeyCode::Delete, KeyCode::Insert, KeyCode::Home, KeyCode::End, KeyCode::PageUp,
+    KeyCode::PageDown, KeyCode::Up, KeyCode::Down, KeyCode::Left, KeyCode::Right,
+    KeyCode::Equal, KeyCode::Comma, KeyCode::Period, KeyCode::Slash, KeyCode::Backslash,
+    KeyCode::GraveAccent, KeyCode::LeftShift, KeyCode::RightShift,
+    KeyCode::LeftControl, KeyCode::RightControl, KeyCode::LeftAlt, KeyCode::RightAlt,
+    KeyCode::KpEnter, KeyCode::KpAdd, KeyCode::KpSubtract, KeyCode::KpMultiply,
+    KeyCode::KpDivide, KeyCode::KpDecimal,
+];
+
+pub fn key_name(key: KeyCode) -> String {
+    format!("{:?}", key)
+}
+
+pub fn key_from_name(name: &str) -> Option<KeyCode> {
+    NAMED_KEYS.iter().copied().find(|key| key_name(*key) == name)
+}
+
+pub fn is_bindable(key: KeyCode) -> bool {
+    NAMED_KEYS.contains(&key)
+}
+
+pub fn is_pressed(action: Action) -> bool {
+    is_key_pressed(settings::get().key_bindings.key(action))
+        || action.pad_button().is_some_and(gamepad::is_pressed)
+}
+
+pub fn is_modifier_down() -> bool {
+    is_key_down(KeyCode::LeftControl) || is_key_down(KeyCode::RightControl)
+        || is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift)
+}
+
+thread_local! {
+    static TYPED: RefCell<Vec<char>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Collects the characters typed since the last frame. Macroquad queues
+/// them until someone reads them, so they're read once here for everyone.
+/// Called once per frame.
+pub fn update() {
+    let mut typed = Vec::new();
+    while let Some(c) = get_char_pressed() {
+        typed.push(c);
+    }
+    // The queue hands out the newest character first.
+    typed.reverse();
+    TYPED.with(|chars| *chars.borrow_mut() = typed);
+}
+
+/// Characters typed this frame, in the order they were typed. They come
+/// from text input rather than key codes, so accented letters and other
+/// keyboard layouts work.
+pub fn typed_chars() -> Vec<char> {
+    TYPED.with(|chars| chars.borrow().clone())
+}
+
+/// Letters of the current language typed this frame, in lower case.
+pub fn typed_letters() -> Vec<char> {
+    let language = i18n::current();
+    typed_chars().into_iter()
+        .flat_map(char::to_lowercase)
+        .filter(|c| language.letter(*c).is_some())
+        .collect()
+}
+
+/// Leaves menu screens: Escape or the controller's East button.
+pub fn is_back_pressed() -> bool {
+    is_key_pressed(KeyCode::Escape) || gamepad::is_pressed(PadButton::East)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binding_a_used_key_swaps_it() {
+        let mut bindings = KeyBindings::default();
+        bindings.bind(Action::Shuffle, KeyCode::Key2);
+        assert_eq!(bindings.key(Action::Shuffle), KeyCode::Key2);
+        assert_eq!(bindings.key(Action::Discard), KeyCode::Key1);
+    }
+
+    #[test]
+    fn binding_a_free_key_leaves_the_others() {
+        let mut bindings = KeyBindings::default();
+        bindings.bind(Action::Hint, KeyCode::F5);
+        assert_eq!(bindings.key(Action::Hint), KeyCode::F5);
+        for action in Action::ALL.into_iter().filter(|&action| action != Action::Hint) {
+            assert_eq!(bindings.key(action), action.default_key());
+        }
+    }
+
+    #[test]
+    fn bindings_are_saved_by_key_name() {
+        let mut bindings = KeyBindings::default();
+        bindings.bind(Action::Pause, KeyCode::F10);
+        let json = serde_json::to_string(&bindings).unwrap();
+        assert!(json.contains(r#""Pause":"F10""#));
+        let loaded: KeyBindings = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.key(Action::Pause), KeyCode::F10);
+    }
+
+    #[test]
+    fn keys_that_type_letters_on_some_keyboards_are_not_bindable() {
+        for key in [KeyCode::Semicolon, KeyCode::Apostrophe, KeyCode::LeftBracket, KeyCode::Minus, KeyCode::A] {
+            assert!(!is_bindable(key), "{:?}", key);
+        }
+    }
+
+    #[test]
+    fn unknown_key_names_keep_the_default() {
+        let loaded: KeyBindings = serde_json::from_str(r#"{"Submit": "NoSuchKey"}"#).unwrap();
+        assert_eq!(loaded.key(Action::Submit), KeyCode::Enter);
+    }
+}
@@ -1,78 +1,120 @@
 use macroquad::prelude::*;
+use crate::game::GameScreen;
+use crate::i18n::{self, tr, tr_args};
+use crate::input;
+use crate::scene::{Context, Scene, Transition};
 use crate::ui::button::Button;
-use crate::ui::screen::{Screen, ScreenManager};
+use crate::ui::layout::{self, Anchor, Rect};
+use crate::ui::theme;
+use crate::ui::widget;
 
-pub fn draw_screen() {
-    clear_background(DARKGRAY);
+pub struct InstructionsScreen {
+    play_button: Button,
+    /// Opened from the pause menu, where the button goes back to the run
+    /// instead of starting a new one.
+    from_pause: bool,
+}
+
+impl InstructionsScreen {
+    pub fn new() -> InstructionsScreen {
+        InstructionsScreen::with_button(tr("title.play"), false)
+    }
+
+    /// The same instructions, read in the middle of a run.
+    pub fn from_pause() -> InstructionsScreen {
+        InstructionsScreen::with_button(tr("common.back"), true)
+    }
+
+    fn with_button(text: &str, from_pause: bool) -> InstructionsScreen {
+        InstructionsScreen {
+            play_button: Button::from_rect(
+                Rect::screen().pad(20.0).anchored(Anchor::Bottom, 200.0, 50.0),
+                theme::current().highlight,
+                text.to_string(),
+                30.0
+            ),
+            from_pause,
+        }
+    }
+}
+
+impl Scene for InstructionsScreen {
+    fn handle_input(&mut self, ctx: &mut Context) -> Transition {
+        widget::focus(&mut [&mut self.play_button]);
+        if self.from_pause {
+            if self.play_button.update() || input::is_back_pressed() {
+                return Transition::Pop;
+            }
+        } else if self.play_button.update() || is_key_pressed(KeyCode::Enter) {
+            return Transition::Replace(GameScreen::start(ctx));
+        }
+        Transition::None
+    }
+
+    fn draw(&self, _ctx: &Context) {
+        draw_instructions();
+        self.play_button.draw();
+    }
+}
+
+fn draw_instructions() {
+    let theme = theme::current();
+    layout::clear(theme.background);
 
     let box_width = 800.0;
-    let box_x = screen_width() / 2.0 - box_width / 2.0;
-    draw_rectangle(box_x, 50.0, box_width, 590.0, WHITE);
+    let box_x = layout::width() / 2.0 - box_width / 2.0;
+    theme::draw_rounded_rect(box_x, 30.0, box_width, 610.0, theme.corner_radius, theme.panel);
 
-    let text_color = BLACK;
+    let text_color = theme.panel_text;
     let header_size = 30.0;
     let text_size = 25.0;
-    let mut y_pos = 80.0;
+    let mut y_pos = 60.0;
     let padding = 40.0;
 
-    draw_text("Instructions", box_x + 20.0, y_pos, header_size, ORANGE);
+    theme::draw_text(tr("instructions.title"), box_x + 20.0, y_pos, header_size, theme.focus);
     y_pos += padding;
-    draw_text(
-        "Create words using the given letter tiles to reach the target score.",
-        box_x + 40.0, y_pos, text_size, text_color
-    );
+    theme::draw_text(tr("instructions.goal"), box_x + 40.0, y_pos, text_size, text_color);
     y_pos += padding - 10.0;
-    draw_text(
-        "You have 4 words per round to meet the score requirement",
-        box_x + 40.0, y_pos, text_size, text_color
-    );
+    theme::draw_text(tr("instructions.words_per_round"), box_x + 40.0, y_pos, text_size, text_color);
 
     y_pos += padding + 10.0;
-    draw_text("Scoring System", box_x + 20.0, y_pos, header_size, BLUE);
+    theme::draw_text(tr("instructions.scoring"), box_x + 20.0, y_pos, header_size, theme.highlight);
     y_pos += padding;
-    let score_texts = [
-        "• Common Letters (10 pts): E, A, T",
-        "• Regular Letters (15 pts): H, I, N, O, S, R",
-        "• Medium Letters (20 pts): D, L",
-        "• Less Common (25 pts): U, F, M, C, G, Y",
-        "• Rare Letters (30 pts): P, B, W",
-        "• Very Rare (35 pts): V, K, J, X, Q, Z",
-        "• Word Score = Total Score of Unique Characters * Word Length"
-    ];
+    // One line per letter value, cheapest first.
+    let letters = &i18n::current().letters;
+    let mut scores: Vec<i32> = letters.iter().map(|letter| letter.score).collect();
+    scores.sort();
+    scores.dedup();
+    let mut score_texts: Vec<String> = scores.into_iter()
+        .map(|score| {
+            let letters: Vec<String> = letters.iter()
+                .filter(|letter| letter.score == score)
+                .map(|letter| i18n::display_letter(letter.letter))
+                .collect();
+            tr_args("instructions.letter_scores", &[("score", &score), ("letters", &letters.join(", "))])
+        })
+        .collect();
+    score_texts.push(tr("instructions.word_score").to_string());
 
     for text in score_texts.iter() {
-        draw_text(text, box_x + 40.0, y_pos, text_size, text_color);
+        theme::draw_text(text, box_x + 40.0, y_pos, text_size, text_color);
         y_pos += padding - 10.0;
     }
 
     y_pos += 10.0;
-    draw_text("Controls", box_x + 20.0, y_pos, header_size, GREEN);
+    theme::draw_text(tr("instructions.controls"), box_x + 20.0, y_pos, header_size, theme.success);
     y_pos += padding;
     let control_texts = [
-        "• Click tiles or type letters to build words",
-        "• Press ENTER or click 'Play Word' to submit",
-        "• Press BACKSPACE or 'X' to clear current word",
-        "• Use 'Shuffle' to rearrange tiles",
-        "• Use 'Discard' to get new tiles (3 per round)"
+        tr("instructions.control_1"),
+        tr("instructions.control_2"),
+        tr("instructions.control_3"),
+        tr("instructions.control_4"),
+        tr("instructions.control_5"),
+        tr("instructions.control_6"),
     ];
 
     for text in control_texts.iter() {
-        draw_text(text, box_x + 40.0, y_pos, text_size, text_color);
+        theme::draw_text(text, box_x + 40.0, y_pos, text_size, text_color);
         y_pos += padding - 10.0;
     }
-
-    let play_button = Button::new(
-        screen_width() / 2.0 - 100.0,
-        650.0,
-        200.0,
-        50.0,
-        BLUE,
-        "Play".to_string(),
-        30.0
-    );
-    play_button.draw();
-
-    if play_button.is_clicked() || is_key_pressed(KeyCode::Enter) {
-        ScreenManager::switch_screen(Screen::Game);
-    }
 }
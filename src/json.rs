@@ -1,15 +1,20 @@
 use std::fs::File;
 use std::io;
 
-pub fn get_available_words() -> Vec<String> {
-    let path = "./src/words_dictionary.json";
-    let file = File::open(path).expect(
-        &*("Could not open file: ".to_owned() + path)
-    );
+/// The words of a dictionary file, in lower case.
+pub fn get_available_words(path: &str) -> Result<Vec<String>, String> {
+    let file = File::open(path).map_err(|e| format!("Could not open {}: {}", path, e))?;
     let reader = io::BufReader::new(file);
-    let words_map: std::collections::HashMap<String, i32> = serde_json::from_reader(reader).expect(
-        "Could not parse JSON file"
-    );
-    
-    words_map.keys().cloned().collect()
+    let words_map: std::collections::HashMap<String, i32> = serde_json::from_reader(reader)
+        .map_err(|e| format!("Could not parse {}: {}", path, e))?;
+
+    Ok(words_map.keys().map(|word| word.to_lowercase()).collect())
+}
+
+/// The words of a dictionary already read into memory, in lower case.
+pub fn words_from_str(contents: &str) -> Result<Vec<String>, String> {
+    let words_map: std::collections::HashMap<String, i32> = serde_json::from_str(contents)
+        .map_err(|e| format!("Could not parse dictionary: {}", e))?;
+
+    Ok(words_map.keys().map(|word| word.to_lowercase()).collect())
 }
@@ -0,0 +1,224 @@
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::tr;
+use crate::mode::GameMode;
+use crate::storage::{self, Area};
+use crate::ui::text_input::TextInput;
+
+pub const MAX_ENTRIES_PER_TABLE: usize = 10;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub name: String,
+    pub score: i32,
+    pub mode: GameMode,
+    pub seed: u64,
+    pub round: u32,
+    pub date: String,
+    /// The run's action log. Together with `mode` and `seed` it is enough to
+    /// replay the run.
+    pub replay: String,
+}
+
+/// Backing storage for leaderboard tables. A table is identified by a game
+/// mode and a seed, and holds at most `MAX_ENTRIES_PER_TABLE` entries.
+pub trait LeaderboardStore: Send {
+    fn submit(&mut self, entry: LeaderboardEntry) -> Result<(), String>;
+
+    /// Best entries for `mode`, highest score first. `None` for `seed` merges
+    /// the tables of every seed played in that mode.
+    fn top(&self, mode: GameMode, seed: Option<u64>, limit: usize)
+        -> Result<Vec<LeaderboardEntry>, String>;
+
+    /// Seeds that have a table in `mode`.
+    fn seeds(&self, mode: GameMode) -> Result<Vec<u64>, String>;
+}
+
+/// Leaderboard kept as a JSON file in the player's data directory, or in
+/// localStorage on the web.
+pub struct LocalLeaderboard {
+    entries: Vec<LeaderboardEntry>,
+}
+
+const FILE: &str = "leaderboard.json";
+
+impl LocalLeaderboard {
+    pub fn open() -> LocalLeaderboard {
+        let entries = storage::get().read(Area::Data, FILE)
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        LocalLeaderboard { entries }
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let serialized = serde_json::to_string_pretty(&self.entries)
+            .map_err(|e| format!("Failed to serialize leaderboard: {}", e))?;
+        storage::get().write(Area::Data, FILE, &serialized)
+            .map_err(|e| format!("Failed to write leaderboard file: {}", e))
+    }
+
+    /// Adds `entry` to its table, which keeps its best
+    /// `MAX_ENTRIES_PER_TABLE` entries.
+    fn insert(&mut self, entry: LeaderboardEntry) {
+        let (mode, seed) = (entry.mode, entry.seed);
+        self.entries.push(entry);
+
+        let mut table: Vec<LeaderboardEntry> = Vec::new();
+        self.entries.retain(|e| {
+            if e.mode == mode && e.seed == seed {
+                table.push(e.clone());
+                false
+            } else {
+                true
+            }
+        });
+        sort_by_score(&mut table);
+        table.truncate(MAX_ENTRIES_PER_TABLE);
+        self.entries.extend(table);
+    }
+}
+
+impl LeaderboardStore for LocalLeaderboard {
+    fn submit(&mut self, entry: LeaderboardEntry) -> Result<(), String> {
+        self.insert(entry);
+        self.save()
+    }
+
+    fn top(&self, mode: GameMode, seed: Option<u64>, limit: usize)
+        -> Result<Vec<LeaderboardEntry>, String> {
+        let mut entries: Vec<LeaderboardEntry> = self.entries.iter()
+            .filter(|e| e.mode == mode && seed.is_none_or(|s| e.seed == s))
+            .cloned()
+            .collect();
+        sort_by_score(&mut entries);
+        entries.truncate(limit);
+        Ok(entries)
+    }
+
+    fn seeds(&self, mode: GameMode) -> Result<Vec<u64>, String> {
+        let mut seeds: Vec<u64> = self.entries.iter()
+            .filter(|e| e.mode == mode)
+            .map(|e| e.seed)
+            .collect();
+        seeds.sort();
+        seeds.dedup();
+        Ok(seeds)
+    }
+}
+
+fn sort_by_score(entries: &mut [LeaderboardEntry]) {
+    entries.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.date.cmp(&b.date)));
+}
+
+/// A finished run waiting for the player to enter a name on the win or lose
+/// screen.
+pub struct PendingRun {
+    pub score: i32,
+    pub mode: GameMode,
+    pub seed: u64,
+    pub round: u32,
+    pub replay: String,
+    pub name: TextInput,
+    pub submitted: bool,
+    /// Cheated runs are never submitted.
+    pub cheated: bool,
+}
+
+impl PendingRun {
+    pub fn new(score: i32, mode: GameMode, seed: u64, round: u32, replay: String, cheated: bool) -> PendingRun {
+        PendingRun {
+            score,
+            mode,
+            seed,
+            round,
+            replay,
+            name: TextInput::new(16),
+            submitted: false,
+            cheated,
+        }
+    }
+}
+
+/// The store in use. Another `LeaderboardStore`, like a remote service, is
+/// picked here.
+static STORE: Lazy<Mutex<Box<dyn LeaderboardStore>>> =
+    Lazy::new(|| Mutex::new(Box::new(LocalLeaderboard::open())));
+
+pub fn store() -> std::sync::MutexGuard<'static, Box<dyn LeaderboardStore>> {
+    STORE.lock().unwrap()
+}
+
+pub fn submit_pending(pending: &mut PendingRun) -> Result<(), String> {
+    if pending.cheated {
+        return Err("Cheated runs aren't ranked".to_string());
+    }
+    let name = pending.name.text().trim();
+    let entry = LeaderboardEntry {
+        name: if name.is_empty() { tr("leaderboard.anonymous").to_string() } else { name.to_string() },
+        score: pending.score,
+        mode: pending.mode,
+        seed: pending.seed,
+        round: pending.round,
+        date: crate::mode::today(),
+        replay: pending.replay.clone(),
+    };
+    store().submit(entry)?;
+    pending.submitted = true;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, score: i32, seed: u64, date: &str) -> LeaderboardEntry {
+        LeaderboardEntry {
+            name: name.to_string(),
+            score,
+            mode: GameMode::Standard,
+            seed,
+            round: 1,
+            date: date.to_string(),
+            replay: String::new(),
+        }
+    }
+
+    fn names(entries: &[LeaderboardEntry]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.name.as_str()).collect()
+    }
+
+    #[test]
+    fn a_table_keeps_its_best_entries() {
+        let mut leaderboard = LocalLeaderboard { entries: Vec::new() };
+        for score in 0..MAX_ENTRIES_PER_TABLE as i32 + 2 {
+            leaderboard.insert(entry(&score.to_string(), score, 1, "2026-10-19"));
+        }
+        let top = leaderboard.top(GameMode::Standard, Some(1), 100).unwrap();
+        assert_eq!(top.len(), MAX_ENTRIES_PER_TABLE);
+        assert_eq!(top[0].score, MAX_ENTRIES_PER_TABLE as i32 + 1);
+        assert_eq!(top[MAX_ENTRIES_PER_TABLE - 1].score, 2);
+    }
+
+    #[test]
+    fn a_full_table_leaves_other_seeds_alone() {
+        let mut leaderboard = LocalLeaderboard { entries: vec![entry("other", 0, 2, "2026-10-19")] };
+        for score in 0..MAX_ENTRIES_PER_TABLE as i32 + 2 {
+            leaderboard.insert(entry(&score.to_string(), score + 1, 1, "2026-10-19"));
+        }
+        assert_eq!(names(&leaderboard.top(GameMode::Standard, Some(2), 100).unwrap()), ["other"]);
+        assert_eq!(leaderboard.seeds(GameMode::Standard).unwrap(), [1, 2]);
+    }
+
+    #[test]
+    fn ties_go_to_the_earlier_run() {
+        let mut leaderboard = LocalLeaderboard { entries: Vec::new() };
+        leaderboard.insert(entry("later", 50, 1, "2026-10-19"));
+        leaderboard.insert(entry("best", 80, 1, "2026-10-20"));
+        leaderboard.insert(entry("earlier", 50, 1, "2026-10-18"));
+        assert_eq!(names(&leaderboard.top(GameMode::Standard, None, 3).unwrap()), ["best", "earlier", "later"]);
+    }
+}
@@ -0,0 +1,200 @@
+use macroquad::prelude::*;
+
+use crate::i18n::{tr, tr_args};
+use crate::input;
+use crate::leaderboard::{self, LeaderboardEntry, PendingRun};
+use crate::mode::GameMode;
+use crate::scene::{Context, Scene, Transition};
+use crate::ui::button::Button;
+use crate::ui::layout::{self, Anchor, Rect};
+use crate::ui::theme;
+use crate::ui::widget::{self, Widget};
+
+pub struct LeaderboardScreen {
+    mode: GameMode,
+    /// Index into the mode's seeds, or `None` for all seeds combined.
+    seed_index: Option<usize>,
+    seeds: Vec<u64>,
+    entries: Vec<LeaderboardEntry>,
+    mode_button: Button,
+    seed_button: Button,
+    back_button: Button,
+}
+
+impl LeaderboardScreen {
+    /// Opens on the tables for `mode`.
+    pub fn new(mode: GameMode) -> LeaderboardScreen {
+        let theme = theme::current();
+        let selectors = Rect::new(layout::width() / 2.0 - 310.0, 100.0, 620.0, 50.0).columns(2, 20.0);
+        let mut screen = LeaderboardScreen {
+            mode,
+            seed_index: None,
+            seeds: Vec::new(),
+            entries: Vec::new(),
+            mode_button: Button::from_rect(selectors[0], theme.button, String::new(), 30.0),
+            seed_button: Button::from_rect(selectors[1], theme.button, String::new(), 30.0),
+            back_button: Button::new(
+                layout::width() / 2.0 - 100.0,
+                630.0,
+                200.0,
+                50.0,
+                theme.button,
+                tr("common.back").to_string(),
+                40.0
+            ),
+        };
+        screen.refresh();
+        screen
+    }
+
+    /// Reloads the table for the current mode and seed.
+    fn refresh(&mut self) {
+        self.seeds = leaderboard::store().seeds(self.mode).unwrap_or_default();
+        let seed = self.seed_index.and_then(|i| self.seeds.get(i).copied());
+        self.entries = leaderboard::store()
+            .top(self.mode, seed, leaderboard::MAX_ENTRIES_PER_TABLE)
+            .unwrap_or_default();
+
+        self.mode_button.set_text(&tr_args("leaderboard.mode", &[("mode", &self.mode.name())]));
+        match seed {
+            Some(seed) => self.seed_button.set_text(&tr_args("leaderboard.seed", &[("seed", &seed)])),
+            None => self.seed_button.set_text(tr("leaderboard.all_seeds")),
+        }
+    }
+}
+
+impl Scene for LeaderboardScreen {
+    fn handle_input(&mut self, _ctx: &mut Context) -> Transition {
+        widget::focus(&mut [&mut self.mode_button, &mut self.seed_button, &mut self.back_button]);
+
+        if self.mode_button.update() {
+            self.mode = self.mode.next();
+            self.seed_index = None;
+            self.refresh();
+        }
+        if self.seed_button.update() {
+            self.seed_index = match self.seed_index {
+                None if !self.seeds.is_empty() => Some(0),
+                Some(i) if i + 1 < self.seeds.len() => Some(i + 1),
+                _ => None,
+            };
+            self.refresh();
+        }
+        if self.back_button.update() || input::is_back_pressed() {
+            return Transition::Pop;
+        }
+        Transition::None
+    }
+
+    fn draw(&self, _ctx: &Context) {
+        let theme = theme::current();
+        layout::clear(theme.background);
+
+        let title = tr("leaderboard.title");
+        let title_dim = theme::measure_text(title, 60);
+        theme::draw_text(title, layout::width() / 2.0 - title_dim.width / 2.0, 70.0, 60.0, theme.text);
+
+        let box_width = 900.0;
+        let box_x = layout::width() / 2.0 - box_width / 2.0;
+        theme::draw_rounded_rect(box_x, 170.0, box_width, 440.0, theme.corner_radius, theme.panel);
+
+        let columns = [("#", 20.0), (tr("leaderboard.name"), 80.0), (tr("leaderboard.score"), 360.0),
+                       (tr("leaderboard.round"), 480.0), (tr("leaderboard.date"), 580.0),
+                       (tr("leaderboard.seed_column"), 740.0)];
+        for (header, offset) in columns {
+            theme::draw_text(header, box_x + offset, 205.0, 30.0, theme.highlight);
+        }
+
+        if self.entries.is_empty() {
+            theme::draw_text(tr("leaderboard.empty"), box_x + 80.0, 250.0, 30.0, theme.panel_text);
+        }
+
+        let mut y_pos = 245.0;
+        for (rank, entry) in self.entries.iter().enumerate() {
+            let cells = [
+                (rank + 1).to_string(),
+                entry.name.clone(),
+                entry.score.to_string(),
+                entry.round.to_string(),
+                entry.date.clone(),
+                entry.seed.to_string(),
+            ];
+            for (cell, (_, offset)) in cells.iter().zip(columns) {
+                theme::draw_text(cell, box_x + offset, y_pos, 25.0, theme.panel_text);
+            }
+            y_pos += 36.0;
+        }
+
+        widget::draw_all(&[&self.mode_button, &self.seed_button, &self.back_button]);
+    }
+}
+
+/// The name prompt for the run that just ended, shown on the win and lose
+/// screens.
+pub struct NameEntry {
+    y: f32,
+    run: PendingRun,
+    submit: Button,
+}
+
+impl NameEntry {
+    /// A prompt for `run` whose score line sits at `y`.
+    pub fn new(y: f32, run: PendingRun) -> NameEntry {
+        let row = Rect::new(layout::width() / 2.0 - 260.0, y + 15.0, 520.0, 50.0);
+        let mut run = run;
+        run.name.set_rect(row.anchored(Anchor::Left, 300.0, 50.0));
+        NameEntry {
+            y,
+            run,
+            submit: Button::from_rect(row.anchored(Anchor::Right, 200.0, 50.0), theme::current().button, tr("leaderboard.submit").to_string(), 35.0),
+        }
+    }
+
+    /// Whether the run is still waiting for a name.
+    pub fn is_entering_name(&self) -> bool {
+        !self.run.submitted && !self.run.cheated
+    }
+
+    /// The Submit button while the prompt is waiting for a name, so it can
+    /// join the screen's focus order.
+    pub fn focusable(&mut self) -> Option<&mut dyn Widget> {
+        if self.is_entering_name() {
+            Some(&mut self.submit)
+        } else {
+            None
+        }
+    }
+
+    /// Handles the player's typing and the Submit button. Returns `true`
+    /// while the prompt is still waiting for the player, so the caller can
+    /// hold back its own Enter handling.
+    pub fn update(&mut self) -> bool {
+        if !self.is_entering_name() {
+            return false;
+        }
+        self.run.name.update();
+        if self.submit.update() || is_key_pressed(KeyCode::Enter) {
+            if let Err(e) = leaderboard::submit_pending(&mut self.run) {
+                tracing::error!("{}", e);
+                self.run.submitted = true;
+            }
+        }
+        true
+    }
+
+    pub fn draw(&self) {
+        let theme = theme::current();
+        let run = &self.run;
+        let score_text = tr_args("leaderboard.run_score", &[("mode", &run.mode.name()), ("score", &run.score)]);
+        layout::draw_centered_text(&score_text, layout::width() / 2.0, self.y, 35.0, theme.panel_text);
+
+        if run.cheated {
+            layout::draw_centered_text(tr("leaderboard.cheated"), layout::width() / 2.0, self.y + 40.0, 30.0, theme.panel_text);
+        } else if run.submitted {
+            layout::draw_centered_text(tr("leaderboard.saved"), layout::width() / 2.0, self.y + 40.0, 30.0, theme.panel_text);
+        } else {
+            run.name.draw();
+            self.submit.draw();
+        }
+    }
+}
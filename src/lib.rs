@@ -0,0 +1,18 @@
+//! The rules and data of Word Quest that don't depend on macroquad, shared
+//! by the window and terminal frontends.
+
+pub mod args;
+pub mod events;
+pub mod i18n;
+pub mod json;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod logging;
+pub mod mode;
+pub mod paths;
+pub mod rules;
+pub mod saved_run;
+pub mod settings_file;
+pub mod storage;
+pub mod unlocks;
+#[cfg(target_arch = "wasm32")]
+mod web;
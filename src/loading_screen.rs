@@ -0,0 +1,117 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use macroquad::prelude::*;
+
+use crate::audio;
+use crate::error_screen::ErrorScreen;
+use crate::i18n::{self, tr, tr_args, Language};
+use crate::scene::{Context, Scene, Transition};
+use crate::title::TitleScreen;
+use crate::ui::label::Label;
+use crate::ui::layout::{self, Anchor, Rect};
+use crate::ui::theme;
+use crate::ui::widget;
+
+/// Builds the scene that comes after loading, once the dictionary is ready.
+type Next = Box<dyn FnOnce(&mut Context, Arc<HashSet<String>>) -> Box<dyn Scene>>;
+
+/// Waits for a dictionary, and at startup for the sounds, while they load
+/// in the background, then moves on to the next scene.
+pub struct LoadingScreen {
+    language: &'static Language,
+    /// Only the first loading screen waits for the sounds, which play as
+    /// soon as they're ready anyway.
+    wait_for_sounds: bool,
+    dictionary_loaded: bool,
+    next: Option<Next>,
+    title: Label,
+}
+
+impl LoadingScreen {
+    /// The first scene: loads the dictionary of the player's language and
+    /// the sounds, then shows the title screen.
+    pub fn startup() -> LoadingScreen {
+        LoadingScreen::new(i18n::current(), true, Box::new(|_, _| Box::new(TitleScreen::new())))
+    }
+
+    fn new(language: &'static Language, wait_for_sounds: bool, next: Next) -> LoadingScreen {
+        LoadingScreen {
+            language,
+            wait_for_sounds,
+            dictionary_loaded: false,
+            next: Some(next),
+            title: Label::new(Rect::new(0.0, layout::height() / 2.0 - 120.0, layout::width(), 80.0),
+                              tr("loading.title"), 60.0)
+                .with_color(theme::current().panel_text),
+        }
+    }
+
+    /// Steps finished out of the steps to take: the dictionary, then each
+    /// sound file.
+    fn progress(&self) -> (usize, usize) {
+        let (sounds_tried, sounds) = if self.wait_for_sounds { audio::loading_progress() } else { (0, 0) };
+        (self.dictionary_loaded as usize + sounds_tried, 1 + sounds)
+    }
+}
+
+/// The scene `next` builds once the dictionary of `language` is loaded. It
+/// comes straight away when the dictionary already is, and after the
+/// loading screen otherwise.
+pub fn when_loaded(ctx: &mut Context, language: &'static Language,
+                   next: impl FnOnce(&mut Context, Arc<HashSet<String>>) -> Box<dyn Scene> + 'static)
+                   -> Box<dyn Scene> {
+    match ctx.words_db(language) {
+        Some(Ok(words_db)) => next(ctx, words_db),
+        Some(Err(e)) => Box::new(dictionary_error(language, e)),
+        None => Box::new(LoadingScreen::new(language, false, Box::new(next))),
+    }
+}
+
+fn dictionary_error(language: &Language, error: String) -> ErrorScreen {
+    ErrorScreen::new(&tr_args("error.dictionary", &[("language", &language.name)]), &error)
+}
+
+impl Scene for LoadingScreen {
+    fn handle_input(&mut self, _ctx: &mut Context) -> Transition {
+        Transition::None
+    }
+
+    fn update(&mut self, ctx: &mut Context) -> Transition {
+        let words_db = match ctx.words_db(self.language) {
+            Some(Ok(words_db)) => words_db,
+            Some(Err(e)) => return Transition::Replace(Box::new(dictionary_error(self.language, e))),
+            None => return Transition::None,
+        };
+        self.dictionary_loaded = true;
+        let (done, steps) = self.progress();
+        if done < steps {
+            return Transition::None;
+        }
+        match self.next.take() {
+            Some(next) => Transition::Replace(next(ctx, words_db)),
+            None => Transition::None,
+        }
+    }
+
+    fn draw(&self, _ctx: &Context) {
+        let theme = theme::current();
+        layout::clear(theme.background);
+        widget::draw_all(&[&self.title]);
+
+        let bar = Rect::screen().anchored(Anchor::Center, 500.0, 30.0);
+        let (done, steps) = self.progress();
+        let filled = bar.w * done as f32 / steps as f32;
+        theme::draw_rounded_rect(bar.x, bar.y, bar.w, bar.h, theme.corner_radius, theme.panel);
+        if filled > 0.0 {
+            theme::draw_rounded_rect(bar.x, bar.y, filled, bar.h, theme.corner_radius, theme.highlight);
+        }
+
+        let status = if self.dictionary_loaded {
+            tr("loading.sounds").to_string()
+        } else {
+            tr_args("loading.dictionary", &[("language", &self.language.name)])
+        };
+        layout::draw_centered_text(&status, layout::width() / 2.0, bar.y + bar.h + 50.0, 28.0, theme.panel_text);
+    }
+}
@@ -0,0 +1,110 @@
+//! Log lines in files in the data directory, through `tracing`. Each line
+//! has a time, a level, where it came from, a message and any fields, e.g.
+//! `2026-10-19 14:03:12.481  INFO ci210_word_game::game: Run started mode=Blitz seed=42`.
+//!
+//! Each day starts a new file, e.g. `word-quest.2026-10-19.log`, and the
+//! last `KEEP` are kept. `WORD_QUEST_LOG` filters what's logged, e.g.
+//! `debug`; it's `info` by default. Warnings and errors also go to stderr.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use once_cell::sync::OnceCell;
+use tracing::Level;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::time::FormatTime;
+use tracing_subscriber::fmt::writer::{MakeWriter, MakeWriterExt};
+use tracing_subscriber::EnvFilter;
+
+const FILE_PREFIX: &str = "word-quest";
+const KEEP: usize = 7;
+/// Lines kept in memory for crash reports.
+const RECENT_LINES: usize = 50;
+
+static DIR: OnceCell<PathBuf> = OnceCell::new();
+static RECENT: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Stamps lines with the player's local time rather than UTC.
+struct LocalTime;
+
+impl FormatTime for LocalTime {
+    fn format_time(&self, w: &mut Writer<'_>) -> std::fmt::Result {
+        write!(w, "{}", chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f"))
+    }
+}
+
+/// Copies every line into `RECENT`.
+struct Recent;
+
+/// A line on its way into `RECENT`, added once it's written.
+#[derive(Default)]
+struct RecentLine(Vec<u8>);
+
+impl io::Write for RecentLine {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for RecentLine {
+    fn drop(&mut self) {
+        let line = String::from_utf8_lossy(&self.0).trim_end().to_string();
+        if let Ok(mut recent) = RECENT.lock() {
+            if recent.len() == RECENT_LINES {
+                recent.pop_front();
+            }
+            recent.push_back(line);
+        }
+    }
+}
+
+impl<'a> MakeWriter<'a> for Recent {
+    type Writer = RecentLine;
+
+    fn make_writer(&'a self) -> RecentLine {
+        RecentLine::default()
+    }
+}
+
+/// Starts logging to `dir`. Fails if logging has already started.
+pub fn init(dir: &Path) -> Result<(), String> {
+    let filter = EnvFilter::try_from_env("WORD_QUEST_LOG").unwrap_or_else(|_| EnvFilter::new("info"));
+    // The appender looks for old files to remove before making the directory.
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let file = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(FILE_PREFIX)
+        .filename_suffix("log")
+        .max_log_files(KEEP)
+        .build(dir)
+        .map_err(|e| e.to_string())?;
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_timer(LocalTime)
+        .with_ansi(false)
+        .with_writer(file.and(Recent).and(io::stderr.with_max_level(Level::WARN)))
+        .try_init()
+        .map_err(|e| e.to_string())?;
+    let _ = DIR.set(dir.to_path_buf());
+    Ok(())
+}
+
+/// Where the log files are, once logging has started.
+pub fn log_dir() -> Option<PathBuf> {
+    DIR.get().cloned()
+}
+
+/// The last lines logged, oldest first. Empty while a line is being added,
+/// so a panic in the middle of logging can't hang whoever asks.
+pub fn recent() -> Vec<String> {
+    RECENT.try_lock().map(|recent| recent.iter().cloned().collect()).unwrap_or_default()
+}
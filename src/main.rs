@@ -1,54 +1,105 @@
 mod ui;
+mod audio;
 mod title;
 mod game;
-mod json;
 mod lose_screen;
 mod round_win_screen;
 mod win_screen;
 mod instructions;
+mod achievements;
+mod achievements_screen;
+mod controls_screen;
+#[cfg(not(target_arch = "wasm32"))]
+mod crash;
+#[cfg(feature = "dev")]
+mod dev;
+mod dictionaries;
+mod error_screen;
+mod gamepad;
+mod input;
+mod leaderboard;
+mod leaderboard_screen;
+mod loading_screen;
+mod pause_screen;
+mod options;
+mod scene;
+mod settings;
+mod settings_screen;
 
+use ci210_word_game::{events, i18n, mode, paths, rules, saved_run, settings_file, storage, unlocks};
 use macroquad::prelude::*;
 
 #[macroquad::main(conf, "Word Quest")]
 async fn main() {
+    // Touches are read through `ui::pointer`, so they shouldn't also move the mouse.
+    simulate_mouse_with_touch(false);
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        if let Err(e) = ci210_word_game::logging::init(&paths::data_dir().join("logs")) {
+            eprintln!("Error: Failed to start logging: {}", e);
+        }
+        crash::init();
+        tracing::info!(version = env!("CARGO_PKG_VERSION"), "Word Quest started");
+        // Words are only logged at debug level, the rest of the run at info.
+        events::subscribe(|event| match event {
+            events::GameEvent::RoundWon { .. } | events::GameEvent::RunWon { .. }
+            | events::GameEvent::RunLost { .. } =>
+                tracing::info!(?event, "Game event"),
+            _ => tracing::debug!(?event, "Game event"),
+        });
+    }
+    achievements::init();
+    if !options::get().no_audio {
+        audio::init();
+    }
+    let (theme, colorblind, language) = {
+        let settings = settings::get();
+        (settings.theme.clone(), settings.colorblind, settings.language.clone())
+    };
+    ui::theme::apply(&theme, colorblind);
+    i18n::apply(&language);
+
+    let mut context = scene::Context::new(options::get());
+    let mut scenes = scene::SceneStack::new(Box::new(loading_screen::LoadingScreen::startup()), &mut context);
+
     loop {
-        match ui::screen::ScreenManager::current_screen() {
-            Some(screen) => {
-                match screen {
-                    ui::screen::Screen::Title => {
-                        title::draw_screen();
-                    },
-                    ui::screen::Screen::Instructions => {
-                        instructions::draw_screen();
-                    }
-                    ui::screen::Screen::Game => unsafe {
-                        game::draw_screen();
-                    },
-                    ui::screen::Screen::LoseScreen => {
-                        lose_screen::draw_screen();
-                    }
-                    ui::screen::Screen::RoundWinScreen => {
-                        round_win_screen::draw_screen();
-                    }
-                    ui::screen::Screen::WinScreen => {
-                        win_screen::draw_screen();
-                    }
-                }
-            }
-            None => {
-                println!("Error: Failed to get current screen");
-                break;
-            }
+        gamepad::update();
+        input::update();
+        audio::update();
+        if is_key_pressed(KeyCode::F11) {
+            settings::update(|settings| settings.fullscreen = !settings.fullscreen);
+            let settings = settings::get();
+            ui::layout::set_window(settings.fullscreen, settings.resolution);
         }
+        ui::layout::begin_frame();
+
+        #[cfg(feature = "dev")]
+        let running = dev::frame(&mut scenes, &mut context);
+        #[cfg(not(feature = "dev"))]
+        let running = scenes.frame(&mut context);
 
+        events::dispatch();
+        ui::toast::draw();
+        ui::layout::end_frame();
+
+        if !running {
+            break;
+        }
         next_frame().await
     }
 }
 
 fn conf() -> Conf {
+    // Read first, as the profile decides which settings are loaded.
+    let options = options::get();
+    let settings = settings::get();
+    let (width, height) = options.size.unwrap_or(settings.resolution);
     Conf {
-        window_width: 1280, 
-        window_height: 720,
+        window_width: width as i32,
+        window_height: height as i32,
+        fullscreen: options.fullscreen || settings.fullscreen,
+        window_resizable: true,
+        high_dpi: true,
         ..Default::default()
     }
 }
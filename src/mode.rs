@@ -0,0 +1,94 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::tr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GameMode {
+    Standard,
+    Endless,
+    Daily,
+    Blitz,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 4] = [
+        GameMode::Standard,
+        GameMode::Endless,
+        GameMode::Daily,
+        GameMode::Blitz,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameMode::Standard => tr("mode.standard"),
+            GameMode::Endless => tr("mode.endless"),
+            GameMode::Daily => tr("mode.daily"),
+            GameMode::Blitz => tr("mode.blitz"),
+        }
+    }
+
+    pub fn next(&self) -> GameMode {
+        let index = GameMode::ALL.iter().position(|m| m == self).unwrap_or(0);
+        GameMode::ALL[(index + 1) % GameMode::ALL.len()]
+    }
+
+    /// Endless runs only end when the player fails a round. Every other
+    /// mode is won after the ruleset's number of rounds.
+    pub fn is_endless(&self) -> bool {
+        *self == GameMode::Endless
+    }
+
+    /// Seconds available per round, or `None` for untimed modes.
+    pub fn round_time(&self) -> Option<f32> {
+        match self {
+            GameMode::Blitz => Some(60.0),
+            _ => None,
+        }
+    }
+
+    /// Daily runs share one seed per calendar day so everybody gets the
+    /// same racks; every other mode rolls a fresh seed per run.
+    pub fn new_seed(&self) -> u64 {
+        match self {
+            GameMode::Daily => daily_seed(),
+            _ => ::rand::random(),
+        }
+    }
+}
+
+/// Parses the English name of a mode, ignoring case, e.g. "blitz".
+impl FromStr for GameMode {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<GameMode, String> {
+        GameMode::ALL.into_iter()
+            .find(|mode| format!("{:?}", mode).eq_ignore_ascii_case(name))
+            .ok_or_else(|| format!("Unknown mode: {}", name))
+    }
+}
+
+/// The player's local date and time.
+fn local_now() -> chrono::NaiveDateTime {
+    #[cfg(target_arch = "wasm32")]
+    {
+        crate::web::local_now()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        chrono::Local::now().naive_local()
+    }
+}
+
+pub fn daily_seed() -> u64 {
+    local_now()
+        .format("%Y%m%d")
+        .to_string()
+        .parse()
+        .unwrap_or(0)
+}
+
+pub fn today() -> String {
+    local_now().format("%Y-%m-%d").to_string()
+}
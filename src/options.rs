@@ -0,0 +1,98 @@
+//! The window version's command line. Options apply to this launch only
+//! and never change the saved settings.
+
+use std::collections::HashSet;
+use std::fs;
+use std::process;
+use std::sync::Arc;
+
+use ci210_word_game::args::Args;
+use ci210_word_game::json;
+use ci210_word_game::mode::GameMode;
+use ci210_word_game::paths;
+use ci210_word_game::rules::Ruleset;
+use once_cell::sync::OnceCell;
+
+const USAGE: &str = "\
+Usage: ci210-word-game [options]
+
+Options:
+  --mode <name>       Start in standard, endless, daily or blitz mode
+  --seed <n>          Deal every run from this seed
+  --rules <file>      A JSON ruleset to play by instead of the game's own
+  --dict <file>       A dictionary to check words against instead of the language's own
+  --fullscreen        Start fullscreen
+  --size <W>x<H>      Start with a window of this size, e.g. 1600x900
+  --profile <name>    Keep saves, settings and stats apart under this name
+  --no-audio          Play without sound
+  -h, --help          Print this help
+  --version           Print the version";
+
+pub struct Options {
+    pub mode: Option<GameMode>,
+    pub seed: Option<u64>,
+    pub ruleset: Ruleset,
+    /// Used for every run whatever the language.
+    pub dictionary: Option<Arc<HashSet<String>>>,
+    pub fullscreen: bool,
+    pub size: Option<(u32, u32)>,
+    pub no_audio: bool,
+}
+
+static OPTIONS: OnceCell<Options> = OnceCell::new();
+
+fn parse_size(size: &str) -> Result<(u32, u32), String> {
+    size.split_once('x')
+        .and_then(|(width, height)| Some((width.parse().ok()?, height.parse().ok()?)))
+        .filter(|&(width, height)| width > 0 && height > 0)
+        .ok_or_else(|| format!("Invalid value for --size: {}, expected e.g. 1600x900", size))
+}
+
+fn load_dictionary(path: &str) -> Result<HashSet<String>, String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    Ok(json::words_from_str(&contents)?.into_iter().collect())
+}
+
+fn parse(args: &Args) -> Result<Options, String> {
+    if let Some(extra) = args.positional.first() {
+        return Err(format!("Unexpected argument: {}", extra));
+    }
+    if let Some(profile) = args.value("profile") {
+        paths::set_profile(profile)?;
+    }
+    Ok(Options {
+        mode: args.value("mode").map(str::parse).transpose()?,
+        seed: args.value("seed").map(|_| args.parse_or("seed", 0)).transpose()?,
+        ruleset: args.ruleset()?,
+        dictionary: args.value("dict").map(load_dictionary).transpose()?.map(Arc::new),
+        fullscreen: args.flag("fullscreen"),
+        size: args.value("size").map(parse_size).transpose()?,
+        no_audio: args.flag("no-audio"),
+    })
+}
+
+/// The command line, read the first time it's asked for, which is before
+/// the window opens. Prints the help or the version and exits when asked
+/// to, and exits with 2 on bad options.
+pub fn get() -> &'static Options {
+    OPTIONS.get_or_init(|| {
+        let flags = ["fullscreen", "no-audio", "help", "version"];
+        let args = Args::parse(std::env::args().skip(1), &flags, &["mode", "seed", "rules", "dict", "size", "profile"])
+            .unwrap_or_else(|e| {
+                eprintln!("Error: {}\n\n{}", e, USAGE);
+                process::exit(2);
+            });
+        if args.flag("help") {
+            println!("{}", USAGE);
+            process::exit(0);
+        }
+        if args.flag("version") {
+            println!("Word Quest {}", env!("CARGO_PKG_VERSION"));
+            process::exit(0);
+        }
+        parse(&args).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            process::exit(2);
+        })
+    })
+}
@@ -0,0 +1,47 @@
+use std::fs;
+use std::path::PathBuf;
+
+use once_cell::sync::OnceCell;
+
+const APP_DIR: &str = "word-quest";
+
+static PROFILE: OnceCell<String> = OnceCell::new();
+
+/// Keeps this session's saves, settings and stats apart from the default
+/// ones, under `profiles/<name>`. Names are letters, digits, `-` and `_`,
+/// and only the first profile set counts.
+pub fn set_profile(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(format!("Invalid profile name: {}", name));
+    }
+    let _ = PROFILE.set(name.to_string());
+    Ok(())
+}
+
+pub fn profile() -> Option<&'static str> {
+    PROFILE.get().map(String::as_str)
+}
+
+fn app_dir(base: Option<PathBuf>) -> PathBuf {
+    let dir = base
+        .map(|dir| dir.join(APP_DIR))
+        .unwrap_or_else(|| PathBuf::from("."));
+    let dir = match profile() {
+        Some(profile) => dir.join("profiles").join(profile),
+        None => dir,
+    };
+    let _ = fs::create_dir_all(&dir);
+    dir
+}
+
+/// Directory for saves, leaderboards and other per-player data. Falls back to
+/// the working directory when the platform has no data directory.
+pub fn data_dir() -> PathBuf {
+    app_dir(dirs::data_dir())
+}
+
+/// Directory for the settings file.
+pub fn config_dir() -> PathBuf {
+    app_dir(dirs::config_dir())
+}
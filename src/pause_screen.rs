@@ -0,0 +1,109 @@
+use crate::game::GameScreen;
+use crate::i18n::{tr, tr_args};
+use crate::input::{self, Action};
+use crate::instructions::InstructionsScreen;
+use crate::mode::GameMode;
+use crate::scene::{Context, Scene, Transition};
+use crate::settings;
+use crate::settings_screen::SettingsScreen;
+use crate::title::TitleScreen;
+use crate::ui::button::Button;
+use crate::ui::layout::{self, Anchor, Rect};
+use crate::ui::modal::Modal;
+use crate::ui::theme;
+use crate::ui::widget;
+
+/// Drawn over the game while it's paused. The game underneath keeps its
+/// state, and its timer stops because only the top scene updates.
+pub struct PauseScreen {
+    resume: Button,
+    restart: Button,
+    settings: Button,
+    how_to_play: Button,
+    abandon: Button,
+    save_and_quit: Button,
+    confirm_abandon: Modal,
+    /// The mode of the paused run, which Restart Run starts again in.
+    mode: GameMode,
+}
+
+impl PauseScreen {
+    pub fn new(mode: GameMode) -> PauseScreen {
+        let buttons = Rect::screen().anchored(Anchor::Center, 540.0, 190.0)
+            .offset(0.0, 155.0)
+            .grid(3, 2, 20.0);
+        let color = theme::current().button;
+        let button = |i: usize, text: &str| Button::from_rect(buttons[i], color, text.to_string(), 35.0);
+        PauseScreen {
+            resume: button(0, tr("pause.resume")),
+            restart: button(1, tr("pause.restart")),
+            settings: button(2, tr("pause.settings")),
+            how_to_play: button(3, tr("pause.how_to_play")),
+            abandon: button(4, tr("pause.abandon")),
+            save_and_quit: button(5, tr("pause.save_and_quit")),
+            confirm_abandon: Modal::new(tr("pause.abandon"), tr("pause.confirm_abandon"),
+                                        &[tr("pause.abandon_choice"), tr("common.cancel")]),
+            mode,
+        }
+    }
+}
+
+impl Scene for PauseScreen {
+    /// Rebuilds the buttons in case the theme changed in the settings.
+    fn resume(&mut self, _ctx: &mut Context) {
+        *self = PauseScreen::new(self.mode);
+    }
+
+    fn handle_input(&mut self, ctx: &mut Context) -> Transition {
+        if self.confirm_abandon.is_open() {
+            if self.confirm_abandon.update() == Some(0) {
+                return Transition::Reset(Box::new(TitleScreen::new()));
+            }
+            return Transition::None;
+        }
+
+        widget::focus(&mut [&mut self.resume, &mut self.restart, &mut self.settings,
+                            &mut self.how_to_play, &mut self.abandon, &mut self.save_and_quit]);
+        if self.resume.update() || input::is_pressed(Action::Pause) {
+            return Transition::Pop;
+        }
+        if self.restart.update() {
+            ctx.mode = self.mode;
+            return Transition::Reset(GameScreen::start(ctx));
+        }
+        if self.settings.update() {
+            return Transition::Push(Box::new(SettingsScreen::new()));
+        }
+        if self.how_to_play.update() {
+            return Transition::Push(Box::new(InstructionsScreen::from_pause()));
+        }
+        if self.abandon.update() {
+            self.confirm_abandon.open();
+        }
+        if self.save_and_quit.update() {
+            ctx.save_requested = true;
+            return Transition::Pop;
+        }
+        Transition::None
+    }
+
+    fn draw(&self, _ctx: &Context) {
+        let theme = theme::current();
+        let screen = Rect::screen();
+        layout::clear(theme.overlay);
+
+        let (center_x, center_y) = screen.center();
+        layout::draw_centered_text(tr("pause.title"), center_x, center_y - 60.0, 80.0, theme.text);
+
+        let key = input::key_name(settings::get().key_bindings.key(Action::Pause));
+        let resume = tr_args("pause.press_to_resume", &[("key", &key)]);
+        layout::draw_centered_text(&resume, center_x, center_y - 10.0, 30.0, theme.text);
+        widget::draw_all(&[&self.resume, &self.restart, &self.settings,
+                           &self.how_to_play, &self.abandon, &self.save_and_quit,
+                           &self.confirm_abandon]);
+    }
+
+    fn is_overlay(&self) -> bool {
+        true
+    }
+}
@@ -1,35 +1,48 @@
-use macroquad::color::{BLACK, GREEN, RED, WHITE};
-use macroquad::input::{is_key_pressed, KeyCode};
-use macroquad::prelude::{clear_background, draw_text, screen_height, screen_width};
-use crate::ui;
+use macroquad::prelude::*;
 
-pub fn draw_screen() {
-    clear_background(GREEN);
+use crate::i18n::tr;
+use crate::scene::{Context, Scene, Transition};
+use crate::ui::button::Button;
+use crate::ui::label::Label;
+use crate::ui::layout::{self, Anchor, Rect};
+use crate::ui::theme;
+use crate::ui::widget;
 
-    let text = "Round Complete";
-    let font_size = 80;
-    let screen_width = screen_width();
-    let screen_height = screen_height();
+/// Shown on top of the game between rounds. Continuing pops back to the
+/// game, which has already dealt the next rack.
+pub struct RoundWinScreen {
+    title: Label,
+    continue_button: Button,
+}
 
-    draw_text(
-        text,
-        screen_width / 2.0 - (text.len() as f32 * font_size as f32 / 4.0),
-        screen_height / 2.0 - 100.0,
-        font_size as f32,
-        BLACK,
-    );
+impl RoundWinScreen {
+    pub fn new() -> RoundWinScreen {
+        let theme = theme::current();
+        RoundWinScreen {
+            title: Label::new(Rect::new(0.0, layout::height() / 2.0 - 160.0, layout::width(), 80.0),
+                              tr("round_win.title"), 80.0)
+                .with_color(theme.panel_text),
+            continue_button: Button::from_rect(
+                Rect::screen().anchored(Anchor::Center, 250.0, 50.0).offset(0.0, 25.0),
+                theme.panel,
+                tr("common.continue").to_string(),
+                40.0
+            ),
+        }
+    }
+}
+
+impl Scene for RoundWinScreen {
+    fn handle_input(&mut self, _ctx: &mut Context) -> Transition {
+        widget::focus(&mut [&mut self.continue_button]);
+        if self.continue_button.update() || is_key_pressed(KeyCode::Enter) {
+            return Transition::Pop;
+        }
+        Transition::None
+    }
 
-    let start_button = ui::button::Button::new(
-        macroquad::prelude::screen_width() / 2.0 - 125.0,
-        macroquad::prelude::screen_height() / 2.0,
-        200.0,
-        50.0,
-        WHITE,
-        "Continue".to_string(),
-        40.0
-    );
-    start_button.draw();
-    if start_button.is_clicked() || is_key_pressed(KeyCode::Enter) {
-        ui::screen::ScreenManager::switch_screen(ui::screen::Screen::Game);
+    fn draw(&self, _ctx: &Context) {
+        layout::clear(theme::current().win_background);
+        widget::draw_all(&[&self.title, &self.continue_button]);
     }
 }
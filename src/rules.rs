@@ -0,0 +1,560 @@
+use std::collections::HashSet;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::events::{self, GameEvent};
+use crate::i18n::Language;
+use crate::json;
+use crate::mode::GameMode;
+use crate::saved_run::SavedRun;
+use crate::unlocks::{Loadout, Modifier, TileSkin};
+
+/// Every rack holds this many letters.
+pub const RACK_SIZE: usize = 12;
+
+/// The numbers a run is played by. The defaults are the game's own, and a
+/// ruleset file can change any of them to try out other tunings.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Ruleset {
+    /// The score to reach in the first round.
+    pub target: u64,
+    /// How much the target goes up after every round.
+    pub target_step: u64,
+    pub words_per_round: u32,
+    pub discards_per_round: u32,
+    pub hints_per_round: u32,
+    /// The rounds needed to win a run. Endless runs ignore it.
+    pub rounds: u32,
+}
+
+impl Default for Ruleset {
+    fn default() -> Ruleset {
+        Ruleset {
+            target: 750,
+            target_step: 100,
+            words_per_round: 4,
+            discards_per_round: 3,
+            hints_per_round: 1,
+            rounds: 5,
+        }
+    }
+}
+
+impl Ruleset {
+    /// Reads a ruleset from a JSON file. Numbers it leaves out keep their defaults.
+    pub fn load(path: &Path) -> Result<Ruleset, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read ruleset {}: {}", path.display(), e))?;
+        serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse ruleset {}: {}", path.display(), e))
+    }
+}
+
+/// What a run looks like after `Run::check`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Playing,
+    /// The round's target was reached and the next round has started.
+    RoundWon,
+    RunWon,
+    RunLost,
+}
+
+/// The answer to asking for a hint.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Hint {
+    /// The best word still playable from the rack.
+    Word(String),
+    NoHintsLeft,
+    NoWords,
+}
+
+/// A move for players that drive a run without a screen, like scripted
+/// players and bots. Serialized as e.g. `{"action": "play", "word": "quiz"}`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Move {
+    Play { word: String },
+    Discard,
+    Shuffle,
+}
+
+/// What a player can see of a run between moves.
+#[derive(Clone, Debug, Serialize)]
+pub struct Observation {
+    pub mode: GameMode,
+    pub seed: u64,
+    pub round: u32,
+    pub rack: Vec<char>,
+    pub target: u64,
+    pub round_score: i32,
+    pub run_score: i32,
+    pub words_remaining: u32,
+    pub discards: u32,
+    /// Sorted, so the same state always reads the same.
+    pub guessed_words: Vec<String>,
+}
+
+/// The state and rules of one run, without anything about how it's shown.
+/// The window and the terminal frontends both play through this, so a seed
+/// deals the same racks in either of them.
+pub struct Run {
+    /// The language the run started in, which decides the tiles and their scores.
+    pub language: &'static Language,
+    letter_weights: WeightedIndex<u32>,
+    words_db: Arc<HashSet<String>>,
+    /// Always seeded from `seed`, so how far it has got is all a save needs
+    /// to keep. The same generator as rand's `StdRng`.
+    rng: ChaCha12Rng,
+    pub ruleset: Ruleset,
+    /// The letters on the rack, left to right. Empty after a discard or a
+    /// won round until the next `deal`.
+    pub rack: Vec<char>,
+    pub mode: GameMode,
+    pub seed: u64,
+    /// The score to reach this round.
+    pub round_score: u64,
+    /// The score made so far this round.
+    pub total_score: i32,
+    pub run_score: i32,
+    pub words_remaining: u32,
+    pub discards: u32,
+    pub discards_used: u32,
+    pub hints_left: u32,
+    pub round: u32,
+    pub guessed_words: HashSet<String>,
+    pub time_left: Option<f32>,
+    /// Every move of the run, for the leaderboard's replays.
+    pub actions: Vec<String>,
+    pub words_per_round: u32,
+    pub discards_per_round: u32,
+    pub modifiers: Vec<Modifier>,
+    pub skin: TileSkin,
+    pub free_invalid_words: bool,
+    /// Whether the developer console changed the run. Cheated runs earn no
+    /// achievements and aren't ranked.
+    pub cheated: bool,
+}
+
+impl Run {
+    /// A fresh run in `mode` with the player's loadout. The first rack is
+    /// dealt by the first `deal`.
+    pub fn new(mode: GameMode, language: &'static Language, words_db: Arc<HashSet<String>>,
+               ruleset: Ruleset, loadout: Loadout, free_invalid_words: bool) -> Run {
+        let letter_weights = WeightedIndex::new(language.letters.iter().map(|letter| letter.weight))
+            .expect("Every language deals at least one letter");
+        let words_per_round = ruleset.words_per_round.saturating_sub(loadout.stake.fewer_words()).max(1);
+        let mut discards_per_round = ruleset.discards_per_round;
+        if loadout.modifiers.contains(&Modifier::ExtraDiscard) {
+            discards_per_round += 1;
+        }
+        let seed = mode.new_seed();
+
+        Run {
+            language,
+            letter_weights,
+            words_db,
+            rng: ChaCha12Rng::seed_from_u64(seed),
+            rack: Vec::new(),
+            mode,
+            seed,
+            round_score: ruleset.target + loadout.stake.target_bonus(),
+            total_score: 0,
+            run_score: 0,
+            words_remaining: words_per_round,
+            discards: discards_per_round,
+            discards_used: 0,
+            hints_left: ruleset.hints_per_round,
+            round: 1,
+            guessed_words: HashSet::new(),
+            time_left: mode.round_time(),
+            actions: Vec::new(),
+            words_per_round,
+            discards_per_round,
+            modifiers: loadout.modifiers,
+            skin: loadout.skin,
+            free_invalid_words,
+            ruleset,
+            cheated: false,
+        }
+    }
+
+    /// Replaces the run's random seed. Only meant for a run that hasn't
+    /// dealt yet, e.g. to replay a seed.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = ChaCha12Rng::seed_from_u64(seed);
+    }
+
+    /// Picks a saved run back up where it was left, rack included.
+    pub fn restore(saved: SavedRun, language: &'static Language, words_db: Arc<HashSet<String>>) -> Run {
+        let loadout = Loadout { skin: saved.skin, modifiers: saved.modifiers.clone(), ..Loadout::default() };
+        let mut run = Run::new(saved.mode, language, words_db, saved.ruleset.clone(), loadout,
+                               saved.free_invalid_words);
+        run.set_seed(saved.seed);
+        run.rng.set_word_pos(saved.rng_position);
+        run.rack = saved.rack;
+        run.round = saved.round;
+        run.round_score = saved.round_score;
+        run.total_score = saved.total_score;
+        run.run_score = saved.run_score;
+        run.words_remaining = saved.words_remaining;
+        run.discards = saved.discards;
+        run.discards_used = saved.discards_used;
+        run.hints_left = saved.hints_left;
+        run.guessed_words = saved.guessed_words.into_iter().collect();
+        run.time_left = saved.time_left;
+        run.actions = saved.actions;
+        run.words_per_round = saved.words_per_round;
+        run.discards_per_round = saved.discards_per_round;
+        run.cheated = saved.cheated;
+        run
+    }
+
+    /// Puts the run aside for Save & Quit. The save keeps how far the
+    /// random number generator has got without drawing from it, so a
+    /// continued run deals the same racks as one never saved.
+    pub fn save(&self) -> SavedRun {
+        SavedRun {
+            language: self.language.name.clone(),
+            mode: self.mode,
+            seed: self.seed,
+            rng_position: self.rng.get_word_pos(),
+            rack: self.rack.clone(),
+            round: self.round,
+            round_score: self.round_score,
+            total_score: self.total_score,
+            run_score: self.run_score,
+            words_remaining: self.words_remaining,
+            discards: self.discards,
+            discards_used: self.discards_used,
+            hints_left: self.hints_left,
+            guessed_words: self.guessed_words.iter().cloned().collect(),
+            time_left: self.time_left,
+            actions: self.actions.clone(),
+            words_per_round: self.words_per_round,
+            discards_per_round: self.discards_per_round,
+            modifiers: self.modifiers.clone(),
+            skin: self.skin,
+            free_invalid_words: self.free_invalid_words,
+            ruleset: self.ruleset.clone(),
+            cheated: self.cheated,
+        }
+    }
+
+    /// Deals a new rack once the old one is gone. Each tile is drawn from
+    /// the language's tile distribution on its own, so a rack can hold a
+    /// letter more than once and words like "book" can be spelled.
+    /// Returns whether it dealt.
+    pub fn deal(&mut self) -> bool {
+        if !self.rack.is_empty() {
+            return false;
+        }
+        self.rack = (0..RACK_SIZE)
+            .map(|_| self.language.letters[self.letter_weights.sample(&mut self.rng)].letter)
+            .collect();
+        true
+    }
+
+    /// Shuffles the rack. Returns the swaps it made, in order, so a
+    /// frontend can move its tiles and anything pointing at them the same way.
+    pub fn shuffle(&mut self) -> Vec<(usize, usize)> {
+        let mut swaps = Vec::new();
+        for i in 0..self.rack.len() {
+            let j = self.rng.gen_range(0..self.rack.len());
+            self.rack.swap(i, j);
+            swaps.push((i, j));
+        }
+        self.actions.push("shuffle".to_string());
+        events::publish(GameEvent::Shuffled);
+        swaps
+    }
+
+    /// Throws the rack away for a new one. Returns false when no discards are left.
+    pub fn discard(&mut self) -> bool {
+        if self.discards == 0 {
+            return false;
+        }
+        self.discards -= 1;
+        self.discards_used += 1;
+        self.rack.clear();
+        self.actions.push("discard".to_string());
+        events::publish(GameEvent::Discarded);
+        true
+    }
+
+    pub fn word_score(&self, word: &str) -> i32 {
+        let mut score = 0;
+        for char in word.chars() {
+            score += self.language.letter(char).map_or(0, |letter| letter.score);
+            if self.modifiers.contains(&Modifier::RareBonus) && self.language.is_rare(char) {
+                score += 50;
+            }
+        }
+        let length = word.chars().count();
+        score *= length as i32;
+        if self.modifiers.contains(&Modifier::LongWords) && length >= 7 {
+            score *= 2;
+        }
+        score
+    }
+
+    /// Whether `word` would score if played: it's in the dictionary, hasn't
+    /// been played this round and isn't one letter over and over.
+    pub fn is_valid_word(&self, word: &str) -> bool {
+        let set: HashSet<_> = word.chars().collect();
+        self.words_db.contains(word) && !self.guessed_words.contains(word) && set.len() > 1
+    }
+
+    /// Whether `word` can be spelled with the letters on the rack.
+    pub fn can_build(&self, word: &str) -> bool {
+        let mut available = self.rack.clone();
+        word.chars().all(|c| match available.iter().position(|&r| r == c) {
+            Some(position) => {
+                available.swap_remove(position);
+                true
+            }
+            None => false,
+        })
+    }
+
+    /// Every word that would score from the current rack, in no particular order.
+    pub fn playable_words(&self) -> impl Iterator<Item = &String> {
+        self.words_db.iter().filter(|word| self.can_build(word) && self.is_valid_word(word))
+    }
+
+    /// The highest scoring word that can still be played from the current rack.
+    pub fn best_word(&self) -> Option<&String> {
+        self.playable_words()
+            .max_by(|a, b| self.word_score(a).cmp(&self.word_score(b))
+                .then_with(|| b.cmp(a)))
+    }
+
+    /// The `count` highest scoring playable words with their scores, best
+    /// first. Ties go alphabetically, like the hint.
+    pub fn top_words(&self, count: usize) -> Vec<(&String, i32)> {
+        let mut words: Vec<_> = self.playable_words().map(|word| (word, self.word_score(word))).collect();
+        words.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        words.truncate(count);
+        words
+    }
+
+    /// The dictionary the run checks words against.
+    pub fn words_db(&self) -> &HashSet<String> {
+        &self.words_db
+    }
+
+    pub fn hint(&mut self) -> Hint {
+        if self.hints_left == 0 {
+            return Hint::NoHintsLeft;
+        }
+        match self.best_word().cloned() {
+            Some(word) => {
+                self.hints_left -= 1;
+                Hint::Word(word)
+            }
+            None => Hint::NoWords,
+        }
+    }
+
+    /// Plays a word built from the rack. Returns its score, or `None` if it
+    /// isn't a word, was already played this round, can't be built from the
+    /// rack, or no words are left.
+    pub fn play_word(&mut self, word: &str) -> Option<i32> {
+        if self.words_remaining == 0 {
+            return None;
+        }
+        self.actions.push(format!("play:{}", word));
+        if self.is_valid_word(word) && self.can_build(word) {
+            let score = self.word_score(word);
+            self.guessed_words.insert(word.to_string());
+            self.words_remaining -= 1;
+            self.total_score += score;
+            let rare = word.chars().any(|c| self.language.is_rare(c));
+            events::publish(GameEvent::WordScored { word: word.to_string(), score, rare });
+            return Some(score);
+        }
+        events::publish(GameEvent::WordRejected { word: word.to_string() });
+        if !self.free_invalid_words {
+            self.words_remaining -= 1;
+        }
+        None
+    }
+
+    /// Makes a move. Returns the score of a played word, or `None` for a
+    /// rejected word and the other moves. A discard with none left does nothing.
+    pub fn make_move(&mut self, action: &Move) -> Option<i32> {
+        match action {
+            Move::Play { word } => self.play_word(word),
+            Move::Discard => {
+                self.discard();
+                None
+            }
+            Move::Shuffle => {
+                self.shuffle();
+                None
+            }
+        }
+    }
+
+    pub fn observe(&self) -> Observation {
+        let mut guessed_words: Vec<String> = self.guessed_words.iter().cloned().collect();
+        guessed_words.sort();
+        Observation {
+            mode: self.mode,
+            seed: self.seed,
+            round: self.round,
+            rack: self.rack.clone(),
+            target: self.round_score,
+            round_score: self.total_score,
+            run_score: self.run_score,
+            words_remaining: self.words_remaining,
+            discards: self.discards,
+            guessed_words,
+        }
+    }
+
+    /// Runs the blitz timer down.
+    pub fn tick(&mut self, dt: f32) {
+        if let Some(time_left) = self.time_left.as_mut() {
+            *time_left -= dt;
+        }
+    }
+
+    /// Checks whether the round or the run is over. A won round resets the
+    /// counters and clears the rack for the next one.
+    pub fn check(&mut self) -> Outcome {
+        if self.total_score >= self.round_score as i32 {
+            events::publish(GameEvent::RoundWon {
+                mode: self.mode,
+                round: self.round,
+                words_used: self.words_per_round - self.words_remaining,
+            });
+            self.run_score += self.total_score;
+            self.round_score += self.ruleset.target_step;
+            self.rack.clear();
+            self.discards = self.discards_per_round;
+            self.words_remaining = self.words_per_round;
+            self.hints_left = self.ruleset.hints_per_round;
+            self.time_left = self.mode.round_time();
+            self.guessed_words = HashSet::new();
+            self.total_score = 0;
+            if !self.mode.is_endless() && self.round >= self.ruleset.rounds {
+                events::publish(GameEvent::RunWon {
+                    mode: self.mode,
+                    score: self.run_score,
+                    discards_used: self.discards_used,
+                });
+                return Outcome::RunWon;
+            }
+            self.round += 1;
+            return Outcome::RoundWon;
+        }
+
+        let out_of_time = self.time_left.is_some_and(|t| t <= 0.0);
+        if self.words_remaining == 0 || out_of_time {
+            self.run_score += self.total_score;
+            events::publish(GameEvent::RunLost {
+                mode: self.mode,
+                round: self.round,
+                score: self.run_score,
+            });
+            return Outcome::RunLost;
+        }
+        Outcome::Playing
+    }
+}
+
+/// Whether the cache was written after the dictionary last changed. With
+/// no dictionary to compare against, the cache is all there is.
+fn cache_is_fresh(language: &Language) -> bool {
+    let modified = |path: &str| fs::metadata(path).and_then(|meta| meta.modified()).ok();
+    match (modified(&language.cache), modified(&language.dictionary)) {
+        (Some(cache), Some(dictionary)) => cache >= dictionary,
+        (cache, None) => cache.is_some(),
+        (None, Some(_)) => false,
+    }
+}
+
+pub fn initialize_words_db(language: &Language) -> Result<HashSet<String>, String> {
+    let cache_file = &language.cache;
+
+    if cache_is_fresh(language) {
+        if let Ok(cache_contents) = fs::read_to_string(cache_file) {
+            if let Ok(cached_words) = serde_json::from_str(&cache_contents) {
+                return Ok(cached_words);
+            }
+        }
+    }
+
+    let db: HashSet<_> = HashSet::from_iter(json::get_available_words(&language.dictionary)?);
+
+    match serde_json::to_string(&db) {
+        Ok(serialized) => {
+            match File::create(cache_file) {
+                Ok(mut file) => {
+                    if let Err(e) = file.write_all(serialized.as_bytes()) {
+                        return Err(format!("Failed to write to cache file: {}", e));
+                    }
+                },
+                Err(e) => return Err(format!("Failed to create or overwrite cache file: {}", e)),
+            }
+        },
+        Err(e) => return Err(format!("Failed to serialize empty words database: {}", e)),
+    }
+
+    Ok(db)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n;
+
+    fn run(seed: u64) -> Run {
+        let words_db = ["cat", "act", "tea"].iter().map(|word| word.to_string()).collect();
+        let mut run = Run::new(GameMode::Standard, i18n::language("English").unwrap(), Arc::new(words_db),
+                               Ruleset::default(), Loadout::default(), false);
+        run.set_seed(seed);
+        run
+    }
+
+    /// Throws the rack away and deals a new one, `count` times.
+    fn redeal(run: &mut Run, count: usize) -> Vec<Vec<char>> {
+        (0..count)
+            .map(|_| {
+                run.rack.clear();
+                run.deal();
+                run.rack.clone()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn a_seed_always_deals_the_same_racks() {
+        assert_eq!(redeal(&mut run(42), 5), redeal(&mut run(42), 5));
+        assert_ne!(redeal(&mut run(42), 5), redeal(&mut run(43), 5));
+    }
+
+    #[test]
+    fn a_restored_run_deals_what_the_saved_one_would_have() {
+        let mut played = run(7);
+        redeal(&mut played, 3);
+        played.shuffle();
+        let json = serde_json::to_string(&played.save()).unwrap();
+        let mut restored = Run::restore(serde_json::from_str(&json).unwrap(), played.language,
+                                        played.words_db.clone());
+        assert_eq!(restored.rack, played.rack);
+        assert_eq!(restored.rng.get_word_pos(), played.rng.get_word_pos());
+        assert_eq!(redeal(&mut restored, 5), redeal(&mut played, 5));
+    }
+}
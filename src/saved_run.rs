@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+use crate::mode::GameMode;
+use crate::rules::Ruleset;
+use crate::storage::{self, Area};
+use crate::unlocks::{Modifier, TileSkin};
+
+/// A run put aside with Save & Quit from the pause menu. The title screen
+/// offers to continue it, and continuing deletes the save so a run can
+/// only be picked up once.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SavedRun {
+    /// The run keeps the tiles and dictionary of the language it started in.
+    pub language: String,
+    pub mode: GameMode,
+    pub seed: u64,
+    /// How far the run's random number generator had got, in words drawn
+    /// since it was seeded from `seed`.
+    pub rng_position: u128,
+    /// The letters on the rack, left to right.
+    pub rack: Vec<char>,
+    pub round: u32,
+    pub round_score: u64,
+    pub total_score: i32,
+    pub run_score: i32,
+    pub words_remaining: u32,
+    pub discards: u32,
+    pub discards_used: u32,
+    pub hints_left: u32,
+    pub guessed_words: Vec<String>,
+    pub time_left: Option<f32>,
+    pub actions: Vec<String>,
+    pub words_per_round: u32,
+    pub discards_per_round: u32,
+    pub modifiers: Vec<Modifier>,
+    pub skin: TileSkin,
+    pub free_invalid_words: bool,
+    pub ruleset: Ruleset,
+    pub cheated: bool,
+}
+
+const FILE: &str = "saved_run.json";
+
+impl SavedRun {
+    /// The saved run, if there is one and it can still be read.
+    pub fn load() -> Option<SavedRun> {
+        storage::get().read(Area::Data, FILE)
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let serialized = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize saved run: {}", e))?;
+        storage::get().write(Area::Data, FILE, &serialized)
+            .map_err(|e| format!("Failed to write saved run: {}", e))
+    }
+
+    pub fn delete() {
+        storage::get().remove(Area::Data, FILE);
+    }
+}
@@ -0,0 +1,296 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use macroquad::prelude::*;
+
+use crate::audio::{self, Music};
+use crate::dictionaries::Dictionaries;
+use crate::i18n::Language;
+use crate::mode::GameMode;
+use crate::options::Options;
+use crate::rules::Ruleset;
+use crate::ui::anim::{self, ease_out};
+use crate::ui::focus;
+use crate::ui::layout::{self, Rect};
+
+const TRANSITION_SECONDS: f32 = 0.3;
+
+/// State shared between scenes. It is handed to every scene call so scenes
+/// don't reach for globals to talk to each other.
+pub struct Context {
+    /// The mode picked on the title screen for the next run.
+    pub mode: GameMode,
+    /// Deals every run from this seed instead of a fresh one.
+    pub seed: Option<u64>,
+    pub ruleset: Ruleset,
+    /// Set by the pause menu's Save & Quit. The run under it saves itself
+    /// and quits when it's back on top.
+    pub save_requested: bool,
+    dictionaries: Dictionaries,
+    /// A dictionary from the command line, used whatever the language.
+    dictionary: Option<Arc<HashSet<String>>>,
+}
+
+impl Context {
+    pub fn new(options: &Options) -> Context {
+        Context {
+            mode: options.mode.unwrap_or(GameMode::Standard),
+            seed: options.seed,
+            ruleset: options.ruleset.clone(),
+            save_requested: false,
+            dictionaries: Dictionaries::default(),
+            dictionary: options.dictionary.clone(),
+        }
+    }
+
+    /// The dictionary of `language`, shared by every run once it's loaded.
+    /// `None` means it's still loading in the background, see
+    /// `Dictionaries::poll`.
+    pub fn words_db(&mut self, language: &'static Language) -> Option<Result<Arc<HashSet<String>>, String>> {
+        if let Some(dictionary) = &self.dictionary {
+            return Some(Ok(dictionary.clone()));
+        }
+        self.dictionaries.poll(language)
+    }
+}
+
+/// What a scene wants the stack to do after this frame.
+pub enum Transition {
+    None,
+    /// Puts a scene on top. The scene underneath keeps its state and comes
+    /// back when the new one pops.
+    Push(Box<dyn Scene>),
+    Pop,
+    /// Swaps the top scene for another one.
+    Replace(Box<dyn Scene>),
+    /// Clears the whole stack and starts over from another scene, e.g. when
+    /// a run is abandoned from the pause menu.
+    Reset(Box<dyn Scene>),
+    Quit,
+}
+
+/// One screen of the game. Only the top scene of the stack gets input and
+/// updates; overlays also draw the scenes beneath them.
+pub trait Scene {
+    fn enter(&mut self, _ctx: &mut Context) {}
+
+    fn exit(&mut self, _ctx: &mut Context) {}
+
+    /// Called when the scene above pops and this one is on top again, so it
+    /// can pick up settings that changed meanwhile.
+    fn resume(&mut self, _ctx: &mut Context) {}
+
+    /// Reads this frame's input. Skipped while a transition is playing.
+    fn handle_input(&mut self, ctx: &mut Context) -> Transition;
+
+    /// Advances anything that moves on its own, like timers.
+    fn update(&mut self, _ctx: &mut Context) -> Transition {
+        Transition::None
+    }
+
+    fn draw(&self, ctx: &Context);
+
+    /// Overlays are drawn on top of the scene below them instead of
+    /// replacing it, e.g. a pause menu over the game.
+    fn is_overlay(&self) -> bool {
+        false
+    }
+
+    /// The background track while this scene is on top. Overlays keep the
+    /// music of the scene beneath them.
+    fn music(&self) -> Music {
+        Music::Menu
+    }
+
+    /// The name the developer overlay shows for the scene.
+    #[cfg(feature = "dev")]
+    fn dev_name(&self) -> &'static str {
+        let name = std::any::type_name::<Self>();
+        name.rsplit("::").next().unwrap_or(name)
+    }
+
+    /// Fields the developer overlay shows while the scene is on the stack.
+    #[cfg(feature = "dev")]
+    fn dev_fields(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    /// Runs a developer console command, split into words. `None` when the
+    /// scene doesn't know the command.
+    #[cfg(feature = "dev")]
+    fn dev_command(&mut self, _args: &[&str]) -> Option<Result<String, String>> {
+        None
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Effect {
+    /// Fades to black and back in, used when a scene is replaced.
+    Fade,
+    /// A pushed scene slides in from the right.
+    SlideIn,
+    /// A popped scene slides back out to the right.
+    SlideOut,
+}
+
+struct Animation {
+    effect: Effect,
+    /// The scene that left the stack, kept around only to be drawn.
+    outgoing: Option<Box<dyn Scene>>,
+    elapsed: f32,
+    duration: f32,
+}
+
+pub struct SceneStack {
+    scenes: Vec<Box<dyn Scene>>,
+    animation: Option<Animation>,
+}
+
+impl SceneStack {
+    pub fn new(mut scene: Box<dyn Scene>, ctx: &mut Context) -> SceneStack {
+        scene.enter(ctx);
+        SceneStack {
+            scenes: vec![scene],
+            animation: None,
+        }
+    }
+
+    /// Runs one frame: input and updates for the top scene, then drawing.
+    /// Returns `false` once a scene asks to quit or the stack is empty.
+    pub fn frame(&mut self, ctx: &mut Context) -> bool {
+        if let Some(animation) = self.animation.as_mut() {
+            animation.elapsed += get_frame_time();
+            if animation.elapsed >= animation.duration {
+                self.animation = None;
+            }
+        }
+
+        if self.animation.is_none() {
+            let Some(top) = self.scenes.last_mut() else {
+                return false;
+            };
+            let mut transition = top.handle_input(ctx);
+            if matches!(transition, Transition::None) {
+                transition = top.update(ctx);
+            }
+            if !self.apply(transition, ctx) {
+                return false;
+            }
+        }
+
+        if let Some(scene) = self.scenes.iter().rev().find(|scene| !scene.is_overlay()) {
+            audio::play_music(scene.music());
+        }
+
+        self.draw(ctx);
+        !self.scenes.is_empty()
+    }
+
+    fn apply(&mut self, transition: Transition, ctx: &mut Context) -> bool {
+        let (effect, outgoing) = match transition {
+            Transition::None => return true,
+            Transition::Quit => return false,
+            Transition::Push(mut scene) => {
+                scene.enter(ctx);
+                let effect = (!scene.is_overlay()).then_some(Effect::SlideIn);
+                self.scenes.push(scene);
+                (effect, None)
+            }
+            Transition::Pop => {
+                let Some(mut scene) = self.scenes.pop() else {
+                    return false;
+                };
+                scene.exit(ctx);
+                let Some(below) = self.scenes.last_mut() else {
+                    return false;
+                };
+                below.resume(ctx);
+                let effect = (!scene.is_overlay()).then_some(Effect::SlideOut);
+                (effect, Some(scene))
+            }
+            Transition::Replace(mut scene) => {
+                let old = self.scenes.pop().map(|mut old| {
+                    old.exit(ctx);
+                    old
+                });
+                scene.enter(ctx);
+                self.scenes.push(scene);
+                (Some(Effect::Fade), old)
+            }
+            Transition::Reset(mut scene) => {
+                for old in self.scenes.iter_mut().rev() {
+                    old.exit(ctx);
+                }
+                // The fade starts from the topmost full screen, since
+                // overlays can't be drawn on their own.
+                let old = self.scenes.drain(..).rev().find(|old| !old.is_overlay());
+                scene.enter(ctx);
+                self.scenes.push(scene);
+                (Some(Effect::Fade), old)
+            }
+        };
+
+        focus::reset();
+        self.animation = effect.filter(|_| anim::enabled()).map(|effect| Animation {
+            effect,
+            outgoing,
+            elapsed: 0.0,
+            duration: anim::scaled(TRANSITION_SECONDS),
+        });
+        true
+    }
+
+    /// The scenes from the top of the stack down, for the developer overlay.
+    #[cfg(feature = "dev")]
+    pub fn scenes_mut(&mut self) -> impl Iterator<Item = &mut Box<dyn Scene>> {
+        self.scenes.iter_mut().rev()
+    }
+
+    /// Draws the top scene, plus everything under it while it's an overlay.
+    fn draw_scenes(&self, ctx: &Context, count: usize) {
+        let first = self.scenes[..count].iter()
+            .rposition(|scene| !scene.is_overlay())
+            .unwrap_or(0);
+        for scene in &self.scenes[first..count] {
+            scene.draw(ctx);
+        }
+    }
+
+    /// Draws the stack without running any scene, e.g. while the developer
+    /// console has the keyboard.
+    pub fn draw(&self, ctx: &Context) {
+        let Some(animation) = self.animation.as_ref() else {
+            self.draw_scenes(ctx, self.scenes.len());
+            return;
+        };
+
+        let t = (animation.elapsed / animation.duration).min(1.0);
+        match animation.effect {
+            Effect::Fade => {
+                // The old scene fades out during the first half and the new
+                // one fades in during the second.
+                match animation.outgoing.as_ref() {
+                    Some(outgoing) if t < 0.5 => outgoing.draw(ctx),
+                    _ => self.draw_scenes(ctx, self.scenes.len()),
+                }
+                let screen = Rect::screen();
+                let alpha = 1.0 - (2.0 * t - 1.0).abs();
+                draw_rectangle(screen.x, screen.y, screen.w, screen.h, Color::new(0.0, 0.0, 0.0, alpha));
+            }
+            Effect::SlideIn => {
+                self.draw_scenes(ctx, self.scenes.len() - 1);
+                layout::set_offset(layout::width() * (1.0 - ease_out(t)), 0.0);
+                self.draw_scenes(ctx, self.scenes.len());
+                layout::set_offset(0.0, 0.0);
+            }
+            Effect::SlideOut => {
+                self.draw_scenes(ctx, self.scenes.len());
+                if let Some(outgoing) = animation.outgoing.as_ref() {
+                    layout::set_offset(layout::width() * ease_out(t), 0.0);
+                    outgoing.draw(ctx);
+                    layout::set_offset(0.0, 0.0);
+                }
+            }
+        }
+    }
+}
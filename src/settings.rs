@@ -0,0 +1,73 @@
+use std::sync::{Mutex, MutexGuard};
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n;
+use crate::input::KeyBindings;
+use crate::settings_file;
+use crate::ui::theme;
+
+/// Window sizes offered on the settings screen, all 16:9 like the canvas.
+pub const RESOLUTIONS: [(u32, u32); 4] = [(1280, 720), (1600, 900), (1920, 1080), (2560, 1440)];
+
+/// Player preferences, saved as JSON in the platform config directory, or in
+/// localStorage on the web.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub key_bindings: KeyBindings,
+    /// The name of the theme in use, built in or from the themes folder.
+    pub theme: String,
+    pub colorblind: bool,
+    pub fullscreen: bool,
+    /// The window size when not fullscreen.
+    pub resolution: (u32, u32),
+    /// Volumes from 0 to 1. Music and effects are scaled by the master volume.
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub effects_volume: f32,
+    /// Multiplies the speed of transitions and other animations. 0 turns
+    /// them off.
+    pub animation_speed: f32,
+    /// The name of the language for the UI text, tiles and dictionary.
+    pub language: String,
+    /// Rejected words don't use up one of the round's words.
+    pub free_invalid_words: bool,
+    /// Asks before quitting from the title screen.
+    pub confirm_quit: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Settings {
+        Settings {
+            key_bindings: KeyBindings::default(),
+            theme: theme::DEFAULT_THEME.to_string(),
+            colorblind: false,
+            fullscreen: false,
+            resolution: RESOLUTIONS[0],
+            master_volume: 1.0,
+            music_volume: 0.7,
+            effects_volume: 1.0,
+            animation_speed: 1.0,
+            language: i18n::DEFAULT_LANGUAGE.to_string(),
+            free_invalid_words: false,
+            confirm_quit: true,
+        }
+    }
+}
+
+static SETTINGS: Lazy<Mutex<Settings>> = Lazy::new(|| Mutex::new(settings_file::load()));
+
+pub fn get() -> MutexGuard<'static, Settings> {
+    SETTINGS.lock().unwrap()
+}
+
+/// Changes the settings and writes them to disk.
+pub fn update(change: impl FnOnce(&mut Settings)) {
+    let mut settings = get();
+    change(&mut settings);
+    if let Err(e) = settings_file::save(&*settings) {
+        tracing::error!("{}", e);
+    }
+}
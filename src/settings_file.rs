@@ -0,0 +1,45 @@
+//! The settings file. The window version keeps all its settings there, and
+//! the terminal version reads the ones that change how a run plays, so both
+//! go through here to find it.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n;
+use crate::storage::{self, Area};
+
+/// The settings the terminal version follows too.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct RunSettings {
+    /// The name of the language for the UI text, tiles and dictionary.
+    pub language: String,
+    /// Rejected words don't use up one of the round's words.
+    pub free_invalid_words: bool,
+}
+
+impl Default for RunSettings {
+    fn default() -> RunSettings {
+        RunSettings {
+            language: i18n::DEFAULT_LANGUAGE.to_string(),
+            free_invalid_words: false,
+        }
+    }
+}
+
+const FILE: &str = "settings.json";
+
+/// The settings file read as a `T`, or the defaults when there's no file or
+/// it can't be read.
+pub fn load<T: DeserializeOwned + Default>() -> T {
+    storage::get().read(Area::Config, FILE)
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+pub fn save<T: Serialize>(settings: &T) -> Result<(), String> {
+    let serialized = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    storage::get().write(Area::Config, FILE, &serialized)
+        .map_err(|e| format!("Failed to write settings file: {}", e))
+}
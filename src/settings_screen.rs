@@ -0,0 +1,193 @@
+use crate::controls_screen::ControlsScreen;
+use crate::i18n::{self, tr, tr_args};
+use crate::input;
+use crate::scene::{Context, Scene, Transition};
+use crate::settings::{self, RESOLUTIONS};
+use crate::ui::button::Button;
+use crate::ui::layout::{self, Rect};
+use crate::ui::slider::Slider;
+use crate::ui::theme;
+use crate::ui::toggle::Toggle;
+use crate::ui::widget::{self, Widget};
+
+/// Every preference in one place, reachable from the title screen and the
+/// pause menu. Changes are saved and applied as soon as they're made.
+pub struct SettingsScreen {
+    fullscreen: Toggle,
+    resolution: Button,
+    theme: Button,
+    colorblind: Toggle,
+    animation_speed: Slider,
+    language: Button,
+    master_volume: Slider,
+    music_volume: Slider,
+    effects_volume: Slider,
+    free_invalid_words: Toggle,
+    confirm_quit: Toggle,
+    key_bindings: Button,
+    back: Button,
+}
+
+impl SettingsScreen {
+    pub fn new() -> SettingsScreen {
+        let theme = theme::current();
+        let settings = settings::get().clone();
+        let center_x = layout::width() / 2.0;
+        let left = Rect::new(center_x - 530.0, 120.0, 520.0, 400.0).rows(6, 20.0);
+        let right = Rect::new(center_x + 10.0, 120.0, 520.0, 400.0).rows(6, 20.0);
+        let percent = |volume: f32| (volume * 100.0).round();
+
+        let mut screen = SettingsScreen {
+            fullscreen: Toggle::new(left[0], tr("settings.fullscreen"), settings.fullscreen),
+            resolution: Button::from_rect(left[1], theme.panel, String::new(), 30.0),
+            theme: Button::from_rect(left[2], theme.panel, String::new(), 30.0),
+            colorblind: Toggle::new(left[3], tr("settings.colorblind"), settings.colorblind),
+            animation_speed: Slider::new(left[4], tr("settings.motion"), settings.animation_speed, 0.0, 2.0, 0.25),
+            language: Button::from_rect(left[5], theme.panel, String::new(), 30.0),
+            master_volume: Slider::new(right[0], tr("settings.master"), percent(settings.master_volume),
+                                       0.0, 100.0, 5.0),
+            music_volume: Slider::new(right[1], tr("settings.music"), percent(settings.music_volume),
+                                      0.0, 100.0, 5.0),
+            effects_volume: Slider::new(right[2], tr("settings.effects"), percent(settings.effects_volume),
+                                        0.0, 100.0, 5.0),
+            free_invalid_words: Toggle::new(right[3], tr("settings.free_invalid_words"),
+                                            settings.free_invalid_words),
+            confirm_quit: Toggle::new(right[4], tr("settings.confirm_quit"), settings.confirm_quit),
+            key_bindings: Button::from_rect(right[5], theme.button, tr("settings.key_bindings").to_string(), 30.0),
+            back: Button::new(center_x - 100.0, layout::height() - 90.0, 200.0, 50.0,
+                              theme.button, tr("common.back").to_string(), 40.0),
+        };
+        screen.refresh();
+        screen
+    }
+
+    /// Updates the buttons that show a setting in their text. Fullscreen
+    /// can also change with F11 while the screen is open.
+    fn refresh(&mut self) {
+        let settings = settings::get();
+        self.fullscreen.set_on(settings.fullscreen);
+        let (width, height) = settings.resolution;
+        self.resolution.set_text(&tr_args("settings.window", &[("width", &width), ("height", &height)]));
+        self.theme.set_text(&tr_args("settings.theme", &[("theme", &settings.theme)]));
+        self.language.set_text(&tr_args("settings.language", &[("language", &settings.language)]));
+    }
+}
+
+impl Scene for SettingsScreen {
+    fn handle_input(&mut self, _ctx: &mut Context) -> Transition {
+        // Each column is listed top to bottom so that every widget gets its
+        // own row: left and right stay free for the sliders.
+        let mut focusable: Vec<&mut dyn Widget> = vec![
+            &mut self.fullscreen, &mut self.resolution, &mut self.theme,
+            &mut self.colorblind, &mut self.animation_speed, &mut self.language,
+            &mut self.master_volume, &mut self.music_volume, &mut self.effects_volume,
+            &mut self.free_invalid_words, &mut self.confirm_quit, &mut self.key_bindings,
+            &mut self.back,
+        ];
+        widget::focus(&mut focusable);
+
+        if self.fullscreen.update() {
+            let fullscreen = self.fullscreen.is_on();
+            settings::update(|settings| settings.fullscreen = fullscreen);
+            apply_window();
+        }
+        if self.resolution.update() {
+            settings::update(|settings| settings.resolution = next(&RESOLUTIONS, &settings.resolution));
+            apply_window();
+        }
+        // A new theme, palette or language changes this screen too, so it's
+        // rebuilt.
+        if self.language.update() {
+            let languages = i18n::available();
+            settings::update(|settings| settings.language = next(&languages, &settings.language));
+            i18n::apply(&settings::get().language);
+            *self = SettingsScreen::new();
+            return Transition::None;
+        }
+        if self.theme.update() {
+            let names: Vec<String> = theme::available().into_iter().map(|theme| theme.name).collect();
+            settings::update(|settings| settings.theme = next(&names, &settings.theme));
+            apply_theme();
+            *self = SettingsScreen::new();
+            return Transition::None;
+        }
+        if self.colorblind.update() {
+            let colorblind = self.colorblind.is_on();
+            settings::update(|settings| settings.colorblind = colorblind);
+            apply_theme();
+            *self = SettingsScreen::new();
+            return Transition::None;
+        }
+
+        if self.animation_speed.update() {
+            let speed = self.animation_speed.value();
+            settings::update(|settings| settings.animation_speed = speed);
+        }
+        if self.master_volume.update() {
+            let volume = self.master_volume.value() / 100.0;
+            settings::update(|settings| settings.master_volume = volume);
+        }
+        if self.music_volume.update() {
+            let volume = self.music_volume.value() / 100.0;
+            settings::update(|settings| settings.music_volume = volume);
+        }
+        if self.effects_volume.update() {
+            let volume = self.effects_volume.value() / 100.0;
+            settings::update(|settings| settings.effects_volume = volume);
+        }
+        if self.free_invalid_words.update() {
+            let free = self.free_invalid_words.is_on();
+            settings::update(|settings| settings.free_invalid_words = free);
+        }
+        if self.confirm_quit.update() {
+            let confirm = self.confirm_quit.is_on();
+            settings::update(|settings| settings.confirm_quit = confirm);
+        }
+        self.refresh();
+
+        if self.key_bindings.update() {
+            return Transition::Push(Box::new(ControlsScreen::new()));
+        }
+        if self.back.update() || input::is_back_pressed() {
+            return Transition::Pop;
+        }
+        Transition::None
+    }
+
+    fn draw(&self, _ctx: &Context) {
+        let theme = theme::current();
+        layout::clear(theme.background);
+
+        let title = tr("settings.title");
+        let title_dim = theme::measure_text(title, 60);
+        theme::draw_text(title, layout::width() / 2.0 - title_dim.width / 2.0, 70.0, 60.0, theme.text);
+
+        widget::draw_all(&[
+            &self.fullscreen, &self.resolution, &self.theme,
+            &self.colorblind, &self.animation_speed, &self.language,
+            &self.master_volume, &self.music_volume, &self.effects_volume,
+            &self.free_invalid_words, &self.confirm_quit, &self.key_bindings,
+            &self.back,
+        ]);
+
+        let note = tr("settings.note");
+        let note_dim = theme::measure_text(note, 25);
+        theme::draw_text(note, layout::width() / 2.0 - note_dim.width / 2.0, 580.0, 25.0, theme.text);
+    }
+}
+
+fn apply_window() {
+    let settings = settings::get();
+    layout::set_window(settings.fullscreen, settings.resolution);
+}
+
+fn apply_theme() {
+    let settings = settings::get();
+    theme::apply(&settings.theme, settings.colorblind);
+}
+
+/// The option after `current`, wrapping around to the first one.
+fn next<T: Clone + PartialEq>(options: &[T], current: &T) -> T {
+    let index = options.iter().position(|option| option == current).map_or(0, |i| i + 1);
+    options[index % options.len()].clone()
+}
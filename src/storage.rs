@@ -0,0 +1,92 @@
+//! Where saves, settings and stats are kept between sessions: files in the
+//! player's directories on desktop, and the browser's localStorage on the
+//! web, which has no file system.
+
+use crate::paths;
+
+/// Which of the player's directories a file belongs in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Area {
+    /// Saves, leaderboards and other per-player data.
+    Data,
+    Config,
+}
+
+pub trait Storage: Sync {
+    /// The contents of the file called `name`, if there is one.
+    fn read(&self, area: Area, name: &str) -> Option<String>;
+    fn write(&self, area: Area, name: &str, contents: &str) -> Result<(), String>;
+    fn remove(&self, area: Area, name: &str);
+}
+
+/// Files in the platform's data and config directories.
+pub struct FileStorage;
+
+impl FileStorage {
+    fn path(area: Area, name: &str) -> std::path::PathBuf {
+        match area {
+            Area::Data => paths::data_dir().join(name),
+            Area::Config => paths::config_dir().join(name),
+        }
+    }
+}
+
+impl Storage for FileStorage {
+    fn read(&self, area: Area, name: &str) -> Option<String> {
+        std::fs::read_to_string(FileStorage::path(area, name)).ok()
+    }
+
+    fn write(&self, area: Area, name: &str, contents: &str) -> Result<(), String> {
+        std::fs::write(FileStorage::path(area, name), contents).map_err(|e| e.to_string())
+    }
+
+    fn remove(&self, area: Area, name: &str) {
+        let _ = std::fs::remove_file(FileStorage::path(area, name));
+    }
+}
+
+/// The browser's localStorage, with keys like `word-quest/data/saved_run.json`,
+/// or `word-quest/profiles/<name>/data/saved_run.json` for a profile.
+#[cfg(target_arch = "wasm32")]
+pub struct LocalStorage;
+
+#[cfg(target_arch = "wasm32")]
+impl LocalStorage {
+    fn key(area: Area, name: &str) -> String {
+        let area = match area {
+            Area::Data => "data",
+            Area::Config => "config",
+        };
+        match paths::profile() {
+            Some(profile) => format!("word-quest/profiles/{}/{}/{}", profile, area, name),
+            None => format!("word-quest/{}/{}", area, name),
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Storage for LocalStorage {
+    fn read(&self, area: Area, name: &str) -> Option<String> {
+        crate::web::local_storage_get(&LocalStorage::key(area, name))
+    }
+
+    fn write(&self, area: Area, name: &str, contents: &str) -> Result<(), String> {
+        crate::web::local_storage_set(&LocalStorage::key(area, name), contents)
+    }
+
+    fn remove(&self, area: Area, name: &str) {
+        crate::web::local_storage_remove(&LocalStorage::key(area, name));
+    }
+}
+
+/// The storage for this platform.
+pub fn get() -> &'static dyn Storage {
+    #[cfg(target_arch = "wasm32")]
+    {
+        &LocalStorage
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        &FileStorage
+    }
+}
@@ -1,55 +1,167 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+
 use macroquad::prelude::*;
 
-use crate::ui;
-
-pub fn draw_screen() {
-    clear_background(DARKGRAY);
-
-    let title = "Word Quest";
-    let title_dim = measure_text(title, None, 80, 1.0);
-    draw_text(
-        title,
-        screen_width() / 2.0 - title_dim.width / 2.0,
-        screen_height() / 2.0 - title_dim.height / 2.0 - 100.0,
-        80.0,
-        WHITE
-    );
-
-    let author = "by kidskoding";
-    let author_dim = measure_text(author, None, 30, 1.0);
-    draw_text(
-        author,
-        screen_width() / 2.0 - author_dim.width / 2.0,
-        screen_height() / 2.0 - author_dim.height / 2.0 - 70.0,
-        30.0,
-        WHITE
-    );
-
-    let start_button = ui::button::Button::new(
-        screen_width() / 2.0 - 100.0,
-        screen_height() / 2.0,
-        200.0,
-        50.0,
-        GOLD,
-        "Play".to_string(),
-        40.0
-    );
-    start_button.draw();
-    if start_button.is_clicked() || is_key_pressed(KeyCode::Enter) {
-        ui::screen::ScreenManager::switch_screen(ui::screen::Screen::Instructions);
+use crate::achievements_screen::AchievementsScreen;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::crash;
+use crate::game::GameScreen;
+use crate::i18n::tr;
+use crate::instructions::InstructionsScreen;
+use crate::leaderboard_screen::LeaderboardScreen;
+use crate::saved_run::SavedRun;
+use crate::scene::{Context, Scene, Transition};
+use crate::settings;
+use crate::settings_screen::SettingsScreen;
+use crate::ui::button::Button;
+use crate::ui::label::Label;
+use crate::ui::layout::{self, Anchor, Rect};
+use crate::ui::modal::Modal;
+use crate::ui::theme;
+use crate::ui::widget::{self, Widget};
+
+pub struct TitleScreen {
+    title: Label,
+    author: Label,
+    play: Button,
+    /// Only shown while there's a run saved from the pause menu.
+    continue_run: Button,
+    saved_run: Option<SavedRun>,
+    mode: Button,
+    leaderboard: Button,
+    achievements: Button,
+    settings: Button,
+    quit: Button,
+    confirm_quit: Modal,
+    /// The report of a crash in the last session, until the player has
+    /// seen the notice about it.
+    #[cfg(not(target_arch = "wasm32"))]
+    crash_report: Option<PathBuf>,
+    #[cfg(not(target_arch = "wasm32"))]
+    crash_notice: Modal,
+}
+
+impl TitleScreen {
+    pub fn new() -> TitleScreen {
+        let theme = theme::current();
+        let center_x = layout::width() / 2.0;
+        let menu = Rect::new(center_x - 210.0, layout::height() / 2.0, 420.0, 275.0).rows(4, 25.0);
+        let single = |row: Rect, text: &str| {
+            Button::from_rect(row.anchored(Anchor::Center, 200.0, row.h), theme.button, text.to_string(), 40.0)
+        };
+        let pair = |row: Rect| row.columns(2, 20.0);
+        let saved_run = SavedRun::load();
+        let play = match saved_run {
+            Some(_) => Button::from_rect(pair(menu[0])[1], theme.button, tr("title.new_run").to_string(), 40.0),
+            None => single(menu[0], tr("title.play")),
+        };
+
+        TitleScreen {
+            title: Label::new(Rect::new(0.0, layout::height() / 2.0 - 200.0, layout::width(), 80.0),
+                              tr("title.name"), 80.0),
+            author: Label::new(Rect::new(0.0, layout::height() / 2.0 - 110.0, layout::width(), 40.0),
+                               tr("title.author"), 30.0),
+            play,
+            continue_run: Button::from_rect(pair(menu[0])[0], theme.button, tr("common.continue").to_string(), 40.0),
+            saved_run,
+            mode: single(menu[1], ""),
+            leaderboard: Button::from_rect(pair(menu[2])[0], theme.button, tr("title.leaderboard").to_string(), 30.0),
+            achievements: Button::from_rect(pair(menu[2])[1], theme.button, tr("title.achievements").to_string(), 30.0),
+            settings: Button::from_rect(pair(menu[3])[0], theme.button, tr("title.settings").to_string(), 35.0),
+            quit: Button::from_rect(pair(menu[3])[1], theme.button, tr("common.quit").to_string(), 40.0),
+            confirm_quit: Modal::new(tr("common.quit"), tr("title.confirm_quit"),
+                                     &[tr("common.quit"), tr("common.cancel")]),
+            #[cfg(not(target_arch = "wasm32"))]
+            crash_report: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            crash_notice: Modal::new(tr("crash.title"), tr("crash.message"),
+                                     &[tr("crash.show_report"), tr("common.continue")]),
+        }
+    }
+}
+
+impl Scene for TitleScreen {
+    fn enter(&mut self, ctx: &mut Context) {
+        self.mode.set_text(ctx.mode.name());
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(report) = crash::take_pending() {
+            tracing::info!(report = %report.display(), "Told the player about the last crash");
+            self.crash_report = Some(report);
+            self.crash_notice.open();
+        }
+    }
+
+    /// Rebuilds the menu in case the theme changed in the settings.
+    fn resume(&mut self, ctx: &mut Context) {
+        *self = TitleScreen::new();
+        self.enter(ctx);
+    }
+
+    fn handle_input(&mut self, ctx: &mut Context) -> Transition {
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.crash_notice.is_open() {
+            if self.crash_notice.update() == Some(0) {
+                if let Some(report) = &self.crash_report {
+                    crash::show_report(report);
+                }
+            }
+            return Transition::None;
+        }
+        if self.confirm_quit.is_open() {
+            if self.confirm_quit.update() == Some(0) {
+                return Transition::Quit;
+            }
+            return Transition::None;
+        }
+
+        let mut focusable: Vec<&mut dyn Widget> = Vec::new();
+        if self.saved_run.is_some() {
+            focusable.push(&mut self.continue_run);
+        }
+        focusable.extend([&mut self.play as &mut dyn Widget, &mut self.mode, &mut self.leaderboard,
+                          &mut self.achievements, &mut self.settings, &mut self.quit]);
+        widget::focus(&mut focusable);
+
+        if self.saved_run.is_some() && self.continue_run.update() {
+            if let Some(saved_run) = self.saved_run.take() {
+                return Transition::Replace(GameScreen::continue_run(ctx, saved_run));
+            }
+        }
+        if self.play.update() || is_key_pressed(KeyCode::Enter) {
+            return Transition::Replace(Box::new(InstructionsScreen::new()));
+        }
+        if self.mode.update() {
+            ctx.mode = ctx.mode.next();
+            self.mode.set_text(ctx.mode.name());
+        }
+        if self.leaderboard.update() {
+            return Transition::Push(Box::new(LeaderboardScreen::new(ctx.mode)));
+        }
+        if self.achievements.update() {
+            return Transition::Push(Box::new(AchievementsScreen::new()));
+        }
+        if self.settings.update() {
+            return Transition::Push(Box::new(SettingsScreen::new()));
+        }
+        if self.quit.update() {
+            if !settings::get().confirm_quit {
+                return Transition::Quit;
+            }
+            self.confirm_quit.open();
+        }
+        Transition::None
     }
 
-    let quit_button = ui::button::Button::new(
-        screen_width() / 2.0 - 100.0,
-        screen_height() / 2.0 + 75.0,
-        200.0,
-        50.0,
-        GOLD,
-        "Quit".to_string(),
-        40.0
-    );
-    quit_button.draw();
-    if quit_button.is_clicked() {
-        std::process::exit(0);
+    fn draw(&self, _ctx: &Context) {
+        layout::clear(theme::current().background);
+        if self.saved_run.is_some() {
+            self.continue_run.draw();
+        }
+        widget::draw_all(&[&self.title, &self.author, &self.play, &self.mode,
+                           &self.leaderboard, &self.achievements, &self.settings, &self.quit,
+                           &self.confirm_quit]);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.crash_notice.draw();
     }
 }
@@ -0,0 +1,334 @@
+//! Word Quest in a terminal. Plays by the same rules as the window version
+//! through `rules::Run`, so a seed deals the same racks in both.
+
+use std::collections::HashSet;
+use std::io;
+use std::process;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use ci210_word_game::args::Args;
+use ci210_word_game::i18n::{self, tr, tr_args};
+use ci210_word_game::mode::GameMode;
+use ci210_word_game::rules::{self, Hint, Outcome, Ruleset, Run};
+use ci210_word_game::settings_file::{self, RunSettings};
+use ci210_word_game::unlocks::{Loadout, Progress};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Alignment, Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Clear, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+
+/// How long to wait for a key before redrawing, which keeps the blitz
+/// timer moving.
+const TICK: Duration = Duration::from_millis(100);
+
+const RACK_ROWS: usize = 3;
+const RACK_COLUMNS: usize = 4;
+
+struct App {
+    run: Run,
+    words_db: Arc<HashSet<String>>,
+    /// What a new run is started with when the player plays again.
+    ruleset: Ruleset,
+    loadout: Loadout,
+    free_invalid_words: bool,
+    /// Indexes into the rack, in the order the letters appear in the word.
+    selected: Vec<usize>,
+    message: String,
+    /// Set once the run is won or lost.
+    finished: Option<Outcome>,
+}
+
+impl App {
+    fn new(mode: GameMode, words_db: Arc<HashSet<String>>, ruleset: Ruleset, loadout: Loadout,
+           free_invalid_words: bool) -> App {
+        let run = Run::new(mode, i18n::current(), words_db.clone(), ruleset.clone(), loadout.clone(),
+                           free_invalid_words);
+        let mut app = App {
+            run,
+            words_db,
+            ruleset,
+            loadout,
+            free_invalid_words,
+            selected: Vec::new(),
+            message: String::new(),
+            finished: None,
+        };
+        app.run.deal();
+        app
+    }
+
+    fn word(&self) -> String {
+        self.selected.iter().map(|&i| self.run.rack[i]).collect()
+    }
+
+    /// Handles a key press. Returns false when the player quits.
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return false;
+        }
+        if self.finished.is_some() {
+            match key.code {
+                KeyCode::Enter => {
+                    *self = App::new(self.run.mode, self.words_db.clone(), self.ruleset.clone(),
+                                     self.loadout.clone(), self.free_invalid_words);
+                }
+                KeyCode::Esc => return false,
+                _ => {}
+            }
+            return true;
+        }
+
+        match key.code {
+            KeyCode::Enter if !self.selected.is_empty() => {
+                let word = self.word();
+                self.message = match self.run.play_word(&word) {
+                    Some(score) => tr_args("tui.scored", &[("word", &word), ("score", &score)]),
+                    None => tr_args("tui.rejected", &[("word", &word)]),
+                };
+                self.selected.clear();
+            }
+            KeyCode::Backspace => {
+                self.selected.pop();
+            }
+            KeyCode::Esc => self.selected.clear(),
+            KeyCode::Char('1') => {
+                for (i, j) in self.run.shuffle() {
+                    for selected in self.selected.iter_mut() {
+                        if *selected == i {
+                            *selected = j;
+                        } else if *selected == j {
+                            *selected = i;
+                        }
+                    }
+                }
+            }
+            KeyCode::Char('2') if self.run.discard() => {
+                self.selected.clear();
+                self.run.deal();
+            }
+            KeyCode::Char('3') => {
+                self.message = match self.run.hint() {
+                    Hint::Word(word) => {
+                        let first = i18n::display_letter(word.chars().next().unwrap_or(' '));
+                        tr_args("game.hint_text", &[("length", &word.chars().count()), ("letter", &first)])
+                    }
+                    Hint::NoHintsLeft => tr("game.no_hints").to_string(),
+                    Hint::NoWords => tr("game.no_words").to_string(),
+                };
+            }
+            // Each typed letter takes the first unused tile showing it.
+            KeyCode::Char(c) => {
+                for letter in c.to_lowercase() {
+                    let unused = (0..self.run.rack.len())
+                        .find(|i| !self.selected.contains(i) && self.run.rack[*i] == letter);
+                    if let Some(i) = unused {
+                        self.selected.push(i);
+                    }
+                }
+            }
+            _ => {}
+        }
+        true
+    }
+
+    fn update(&mut self, dt: f32) {
+        if self.finished.is_none() {
+            self.run.tick(dt);
+            match self.run.check() {
+                Outcome::Playing => {}
+                Outcome::RoundWon => {
+                    self.selected.clear();
+                    self.message = tr("round_win.title").to_string();
+                    self.run.deal();
+                }
+                outcome => {
+                    self.selected.clear();
+                    self.finished = Some(outcome);
+                }
+            }
+        }
+    }
+}
+
+fn draw(frame: &mut Frame, app: &App) {
+    let run = &app.run;
+    let [header, body, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ]).areas(frame.area());
+
+    let run_info = tr_args("game.run_info", &[
+        ("mode", &run.mode.name()),
+        ("seed", &run.seed),
+        ("score", &run.run_score),
+    ]);
+    frame.render_widget(Paragraph::new(run_info).style(Style::new().add_modifier(Modifier::BOLD)), header);
+
+    let [hud, board] = Layout::horizontal([Constraint::Length(26), Constraint::Min(0)]).areas(body);
+    draw_hud(frame, run, hud);
+
+    let [rack, word, message] = Layout::vertical([
+        Constraint::Length(4 * RACK_ROWS as u16),
+        Constraint::Length(3),
+        Constraint::Length(1),
+    ]).areas(board);
+    draw_rack(frame, app, rack);
+
+    let letters: Vec<String> = app.word().chars().map(i18n::display_letter).collect();
+    frame.render_widget(
+        Paragraph::new(letters.join(" "))
+            .alignment(Alignment::Center)
+            .block(Block::bordered().title(tr("tui.word"))),
+        word,
+    );
+    frame.render_widget(Paragraph::new(app.message.as_str()).alignment(Alignment::Center), message);
+
+    let controls = if app.finished.is_some() { tr("tui.end_controls") } else { tr("tui.controls") };
+    frame.render_widget(Paragraph::new(controls).style(Style::new().fg(Color::DarkGray)), footer);
+
+    if let Some(outcome) = app.finished {
+        let title = if outcome == Outcome::RunWon { tr("win.title") } else { tr("lose.title") };
+        let score = tr_args("game.run_info", &[
+            ("mode", &run.mode.name()),
+            ("seed", &run.seed),
+            ("score", &run.run_score),
+        ]);
+        let area = centered(body, score.chars().count() as u16 + 6, 5);
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new(vec![Line::from(""), Line::from(score)])
+                .alignment(Alignment::Center)
+                .block(Block::bordered().title(title).title_alignment(Alignment::Center)),
+            area,
+        );
+    }
+}
+
+fn draw_hud(frame: &mut Frame, run: &Run, area: Rect) {
+    let counter = |label: &str, value: String| Line::from(vec![
+        Span::raw(format!("{}: ", label)),
+        Span::styled(value, Style::new().add_modifier(Modifier::BOLD)),
+    ]);
+    let mut lines = vec![
+        counter(tr("game.score_at_least"), run.round_score.to_string()),
+        counter(tr("game.round_score"), run.total_score.to_string()),
+        Line::from(""),
+        counter(tr("game.words"), run.words_remaining.to_string()),
+        counter(tr("game.discards"), run.discards.to_string()),
+        counter(tr("game.round"), run.round.to_string()),
+    ];
+    if let Some(time_left) = run.time_left {
+        let time = tr_args("game.time", &[("seconds", &(time_left.ceil() as i32))]);
+        let color = if time_left < 10.0 { Color::Red } else { Color::Reset };
+        lines.push(Line::from(""));
+        lines.push(Line::styled(time, Style::new().fg(color)));
+    }
+    frame.render_widget(Paragraph::new(lines).block(Block::bordered()), area);
+}
+
+/// The rack as a 3x4 grid of tiles with their scores. Tiles already in
+/// the word are greyed out.
+fn draw_rack(frame: &mut Frame, app: &App, area: Rect) {
+    let rows = Layout::vertical([Constraint::Length(4); RACK_ROWS]).split(area);
+    for (row, row_area) in rows.iter().enumerate() {
+        let cells = Layout::horizontal([Constraint::Length(8); RACK_COLUMNS]).split(*row_area);
+        for (column, cell) in cells.iter().enumerate() {
+            let index = row * RACK_COLUMNS + column;
+            let Some(&letter) = app.run.rack.get(index) else {
+                continue;
+            };
+            let score = app.run.language.letter(letter).map_or(0, |letter| letter.score);
+            let style = if app.selected.contains(&index) {
+                Style::new().fg(Color::DarkGray)
+            } else {
+                Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            };
+            frame.render_widget(
+                Paragraph::new(vec![Line::from(i18n::display_letter(letter)), Line::from(score.to_string())])
+                    .alignment(Alignment::Center)
+                    .style(style)
+                    .block(Block::bordered().border_style(style)),
+                *cell,
+            );
+        }
+    }
+}
+
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(area.x + (area.width - width) / 2, area.y + (area.height - height) / 2, width, height)
+}
+
+fn play(terminal: &mut DefaultTerminal, mut app: App) -> io::Result<()> {
+    let mut last = Instant::now();
+    loop {
+        terminal.draw(|frame| draw(frame, &app))?;
+        if event::poll(TICK)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press && !app.handle_key(key) {
+                    return Ok(());
+                }
+            }
+        }
+        let now = Instant::now();
+        app.update((now - last).as_secs_f32());
+        last = now;
+    }
+}
+
+const USAGE: &str = "Usage: word-quest-tui [standard|endless|daily|blitz] [--rules <file>]";
+
+fn main() {
+    let args = match Args::parse(std::env::args().skip(1), &["help"], &["rules"]) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+    if args.flag("help") {
+        println!("{}", USAGE);
+        return;
+    }
+    let mode = match args.positional.first() {
+        None => GameMode::Standard,
+        Some(name) => match name.parse() {
+            Ok(mode) => mode,
+            Err(_) => {
+                eprintln!("{}", USAGE);
+                process::exit(2);
+            }
+        },
+    };
+    let ruleset = match args.ruleset() {
+        Ok(ruleset) => ruleset,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let settings: RunSettings = settings_file::load();
+    i18n::apply(&settings.language);
+    let words_db = match rules::initialize_words_db(i18n::current()) {
+        Ok(words_db) => Arc::new(words_db),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let app = App::new(mode, words_db, ruleset, Progress::load().loadout, settings.free_invalid_words);
+    let mut terminal = ratatui::init();
+    let result = play(&mut terminal, app);
+    ratatui::restore();
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,165 @@
+use macroquad::prelude::*;
+
+use crate::settings;
+use crate::ui::layout::Rect;
+
+/// `seconds` at the player's animation speed. A speed of 0 turns animations
+/// off, so everything finishes at once.
+pub fn scaled(seconds: f32) -> f32 {
+    let speed = settings::get().animation_speed;
+    if speed > 0.0 {
+        seconds / speed
+    } else {
+        0.0
+    }
+}
+
+pub fn enabled() -> bool {
+    settings::get().animation_speed > 0.0
+}
+
+/// Starts fast and slows down towards the end.
+pub fn ease_out(t: f32) -> f32 {
+    1.0 - (1.0 - t) * (1.0 - t)
+}
+
+/// Moves a rectangle from one place to another. Tweens only describe where
+/// something is; whoever owns one advances it and reads `rect` back, so
+/// nothing waits for them to finish.
+#[derive(Clone, Copy, Debug)]
+pub struct Tween {
+    from: Rect,
+    to: Rect,
+    delay: f32,
+    elapsed: f32,
+    duration: f32,
+}
+
+impl Tween {
+    pub fn new(from: Rect, to: Rect, seconds: f32) -> Tween {
+        Tween {
+            from,
+            to,
+            delay: 0.0,
+            elapsed: 0.0,
+            duration: scaled(seconds),
+        }
+    }
+
+    /// Holds the tween at its start for `seconds`, e.g. to stagger a row.
+    pub fn with_delay(mut self, seconds: f32) -> Tween {
+        self.delay = scaled(seconds);
+        self
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.elapsed += dt;
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.elapsed >= self.delay + self.duration
+    }
+
+    pub fn rect(&self) -> Rect {
+        if self.is_done() {
+            return self.to;
+        }
+        let t = ease_out(((self.elapsed - self.delay) / self.duration).clamp(0.0, 1.0));
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+        Rect::new(
+            lerp(self.from.x, self.to.x),
+            lerp(self.from.y, self.to.y),
+            lerp(self.from.w, self.to.w),
+            lerp(self.from.h, self.to.h),
+        )
+    }
+}
+
+/// A wobble that dies down, for the word box or the whole screen.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Shake {
+    strength: f32,
+    elapsed: f32,
+    duration: f32,
+}
+
+impl Shake {
+    pub fn start(&mut self, strength: f32, seconds: f32) {
+        if enabled() {
+            *self = Shake {
+                strength,
+                elapsed: 0.0,
+                duration: scaled(seconds),
+            };
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.elapsed = (self.elapsed + dt).min(self.duration);
+    }
+
+    /// How far to move what's shaking this frame.
+    pub fn offset(&self) -> (f32, f32) {
+        if self.elapsed >= self.duration {
+            return (0.0, 0.0);
+        }
+        let falloff = 1.0 - self.elapsed / self.duration;
+        let amount = self.strength * falloff;
+        ((self.elapsed * 55.0).sin() * amount, (self.elapsed * 41.0).cos() * amount * 0.5)
+    }
+}
+
+struct Particle {
+    position: Vec2,
+    velocity: Vec2,
+    life: f32,
+    max_life: f32,
+    size: f32,
+    color: Color,
+}
+
+/// Confetti that bursts out of a point, falls and fades.
+#[derive(Default)]
+pub struct Particles {
+    particles: Vec<Particle>,
+}
+
+impl Particles {
+    const GRAVITY: f32 = 600.0;
+
+    pub fn burst(&mut self, (x, y): (f32, f32), count: usize, colors: &[Color]) {
+        if !enabled() || colors.is_empty() {
+            return;
+        }
+        for i in 0..count {
+            let angle = rand::gen_range(0.0, std::f32::consts::TAU);
+            let speed = rand::gen_range(150.0, 450.0);
+            let life = scaled(rand::gen_range(0.6, 1.2));
+            self.particles.push(Particle {
+                position: vec2(x, y),
+                velocity: vec2(angle.cos(), angle.sin()) * speed,
+                life,
+                max_life: life,
+                size: rand::gen_range(3.0, 7.0),
+                color: colors[i % colors.len()],
+            });
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        for particle in &mut self.particles {
+            particle.velocity.y += Self::GRAVITY * dt;
+            particle.position += particle.velocity * dt;
+            particle.life -= dt;
+        }
+        self.particles.retain(|particle| particle.life > 0.0);
+    }
+
+    pub fn draw(&self) {
+        for particle in &self.particles {
+            let mut color = particle.color;
+            color.a *= particle.life / particle.max_life;
+            draw_rectangle(particle.position.x, particle.position.y, particle.size, particle.size, color);
+        }
+    }
+}
@@ -1,17 +1,30 @@
 use macroquad::prelude::*;
 
+use crate::ui::label;
+use crate::ui::layout::{Anchor, Rect};
+use crate::ui::pointer;
+use crate::ui::theme::{self, Theme};
+use crate::ui::widget::{FocusSlot, Widget};
+
 pub struct Button {
     x: f32,
     y: f32,
     width: f32,
     height: f32,
     color: Color,
+    /// Overrides the theme's corner radius and button text color, for tiles.
+    radius: Option<f32>,
+    text_color: Option<Color>,
     text: String,
     font_size: f32,
+    focused: bool,
+    enabled: bool,
+    pressed: bool,
+    focus: FocusSlot,
 }
 
 impl Button {
-    pub fn new(x: f32, y: f32, width: f32, height: f32, 
+    pub fn new(x: f32, y: f32, width: f32, height: f32,
                color: Color, text: String, font_size: f32) -> Button {
         Button {
             x,
@@ -19,51 +32,114 @@ impl Button {
             width,
             height,
             color,
+            radius: None,
+            text_color: None,
             text,
             font_size,
+            focused: false,
+            enabled: true,
+            pressed: false,
+            focus: FocusSlot::default(),
         }
     }
-    
+
+    pub fn from_rect(rect: Rect, color: Color, text: String, font_size: f32) -> Button {
+        Button::new(rect.x, rect.y, rect.w, rect.h, color, text, font_size)
+    }
+
+    pub fn with_style(mut self, radius: f32, text_color: Color) -> Button {
+        self.radius = Some(radius);
+        self.text_color = Some(text_color);
+        self
+    }
+
     pub fn draw(&self) {
-        draw_rectangle(self.x, self.y, self.width, self.height, self.color);
-        let text_dim = measure_text(&self.text, None,
-                                    self.font_size as u16, 1.0);
-        draw_text(
-            &self.text,
-            self.x + self.width / 2.0 - text_dim.width / 2.0,
-            self.y + self.height / 2.0 - text_dim.height / 2.0 + 20.0,
-            self.font_size,
-            BLACK
-        );
-    }
-    
+        let theme = theme::current();
+        let color = if !self.enabled {
+            theme.disabled
+        } else if self.pressed {
+            Theme::shade(self.color, theme.pressed_shade)
+        } else if self.is_hovered() {
+            Theme::shade(self.color, theme.hover_shade)
+        } else {
+            self.color
+        };
+        let radius = self.radius.unwrap_or(theme.corner_radius);
+        theme::draw_rounded_rect(self.x, self.y, self.width, self.height, radius, color);
+        if self.focused || self.focus.is_focused() {
+            draw_rectangle_lines(self.x - 5.0, self.y - 5.0,
+                                 self.width + 10.0, self.height + 10.0,
+                                 6.0, theme.focus);
+        }
+        label::draw_aligned(&self.text, self.rect(), Anchor::Center, self.font_size,
+                           self.text_color.unwrap_or(theme.button_text));
+    }
+
     pub fn is_clicked(&self) -> bool {
-        let mouse_pos = mouse_position();
+        self.enabled && pointer::is_pressed() && self.is_hovered()
+    }
+
+    /// Tracks the pressed state for a button kept between frames. Returns
+    /// `true` when it was clicked or activated through focus.
+    pub fn update(&mut self) -> bool {
+        if !self.enabled {
+            self.pressed = false;
+            return false;
+        }
+        if pointer::is_pressed() {
+            self.pressed = self.is_hovered();
+        } else if !pointer::is_down() {
+            self.pressed = false;
+        }
+        self.is_clicked() || self.focus.is_activated()
+    }
 
-        is_mouse_button_pressed(MouseButton::Left)
-            && mouse_pos.0 >= self.x
-            && mouse_pos.0 <= self.x + self.width
-            && mouse_pos.1 >= self.y
-            && mouse_pos.1 <= self.y + self.height
+    pub fn is_hovered(&self) -> bool {
+        pointer::is_over(self.rect())
     }
-    
-    pub fn get_x(&self) -> f32 {
-        self.x
+
+    /// Marks the button as the target of keyboard or controller navigation.
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    /// Disabled buttons are grayed out and ignore clicks.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        if self.text != text {
+            self.text = text.to_string();
+        }
+    }
+
+    pub fn set_color(&mut self, color: Color) {
+        self.color = color;
     }
-    pub fn get_y(&self) -> f32 {
-        self.y
+
+    pub fn rect(&self) -> Rect {
+        Rect::new(self.x, self.y, self.width, self.height)
     }
-    pub fn get_width(&self) -> f32 {
-        self.width
+
+    pub fn set_rect(&mut self, rect: Rect) {
+        self.x = rect.x;
+        self.y = rect.y;
+        self.width = rect.w;
+        self.height = rect.h;
     }
-    pub fn get_height(&self) -> f32 {
-        self.height
+}
+
+impl Widget for Button {
+    fn rect(&self) -> Rect {
+        Button::rect(self)
     }
-    
-    pub fn set_x(&mut self, x: f32) {
-        self.x = x;
+
+    fn draw(&self) {
+        Button::draw(self);
     }
-    pub fn set_y(&mut self, y: f32) {
-        self.y = y;
+
+    fn focus_slot(&mut self) -> Option<&mut FocusSlot> {
+        self.enabled.then_some(&mut self.focus)
     }
 }
@@ -0,0 +1,93 @@
+use std::sync::Mutex;
+
+use macroquad::prelude::*;
+
+use crate::input::{self, Action};
+use crate::ui::pointer;
+
+/// Keyboard and controller navigation over a screen's focusable widgets.
+/// Widgets are numbered in reading order and laid out in rows; the cursor
+/// only shows once a direction is pressed and hides again on mouse clicks.
+struct FocusState {
+    index: usize,
+    visible: bool,
+    activated: bool,
+}
+
+static FOCUS: Mutex<FocusState> = Mutex::new(FocusState {
+    index: 0,
+    visible: false,
+    activated: false,
+});
+
+/// Handles this frame's navigation input. `rows` holds the number of
+/// widgets in each row, top to bottom. Empty rows, like one whose only
+/// button is hidden, are skipped. Call before drawing the widgets.
+pub fn begin(rows: &[usize]) {
+    let rows: Vec<usize> = rows.iter().copied().filter(|&length| length > 0).collect();
+    let mut focus = FOCUS.lock().unwrap();
+    let count: usize = rows.iter().sum();
+    if count == 0 {
+        focus.visible = false;
+        focus.activated = false;
+        return;
+    }
+    focus.index = focus.index.min(count - 1);
+
+    let (mut row, mut column) = position(&rows, focus.index);
+    let mut moved = true;
+    if input::is_pressed(Action::CursorLeft) {
+        column = (column + rows[row] - 1) % rows[row];
+    } else if input::is_pressed(Action::CursorRight) {
+        column = (column + 1) % rows[row];
+    } else if input::is_pressed(Action::CursorUp) {
+        row = (row + rows.len() - 1) % rows.len();
+    } else if input::is_pressed(Action::CursorDown) {
+        row = (row + 1) % rows.len();
+    } else {
+        moved = false;
+    }
+
+    if moved {
+        // The first press only reveals the cursor where it was.
+        if focus.visible {
+            column = column.min(rows[row].saturating_sub(1));
+            focus.index = rows[..row].iter().sum::<usize>() + column;
+        }
+        focus.visible = true;
+    }
+    if pointer::is_pressed() {
+        focus.visible = false;
+    }
+    focus.activated = focus.visible && input::is_pressed(Action::SelectTile);
+}
+
+fn position(rows: &[usize], index: usize) -> (usize, usize) {
+    let mut first = 0;
+    for (row, length) in rows.iter().enumerate() {
+        if index < first + length {
+            return (row, index - first);
+        }
+        first += length;
+    }
+    (0, 0)
+}
+
+pub fn is_focused(index: usize) -> bool {
+    let focus = FOCUS.lock().unwrap();
+    focus.visible && focus.index == index
+}
+
+/// Whether the select key or button was pressed on widget `index` this frame.
+pub fn is_activated(index: usize) -> bool {
+    let focus = FOCUS.lock().unwrap();
+    focus.activated && focus.index == index
+}
+
+/// Forgets the cursor, e.g. when switching screens.
+pub fn reset() {
+    let mut focus = FOCUS.lock().unwrap();
+    focus.index = 0;
+    focus.visible = false;
+    focus.activated = false;
+}
@@ -0,0 +1,47 @@
+use macroquad::prelude::*;
+
+use crate::ui::layout::{Anchor, Rect};
+use crate::ui::theme;
+use crate::ui::widget::Widget;
+
+pub struct Label {
+    rect: Rect,
+    text: String,
+    font_size: f32,
+    color: Color,
+}
+
+impl Label {
+    /// A label centered in `rect` in the theme's text color.
+    pub fn new(rect: Rect, text: &str, font_size: f32) -> Label {
+        Label {
+            rect,
+            text: text.to_string(),
+            font_size,
+            color: theme::current().text,
+        }
+    }
+
+    pub fn with_color(mut self, color: Color) -> Label {
+        self.color = color;
+        self
+    }
+}
+
+impl Widget for Label {
+    fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    fn draw(&self) {
+        draw_aligned(&self.text, self.rect, Anchor::Center, self.font_size, self.color);
+    }
+}
+
+/// Draws `text` at `anchor` inside `rect`, measuring the glyphs so it sits
+/// in the middle of the rectangle at any font size.
+pub fn draw_aligned(text: &str, rect: Rect, anchor: Anchor, font_size: f32, color: Color) {
+    let text_dim = theme::measure_text(text, font_size as u16);
+    let placed = rect.anchored(anchor, text_dim.width, text_dim.offset_y);
+    theme::draw_text(text, placed.x, placed.y + text_dim.offset_y, font_size, color);
+}
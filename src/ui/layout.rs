@@ -0,0 +1,197 @@
+use std::cell::Cell;
+
+use macroquad::prelude::*;
+
+use crate::ui::theme;
+
+/// Every screen is laid out on this virtual canvas, which is then scaled to
+/// fit the window with black bars on the sides that don't match its aspect
+/// ratio.
+pub const VIRTUAL_WIDTH: f32 = 1280.0;
+pub const VIRTUAL_HEIGHT: f32 = 720.0;
+
+pub fn width() -> f32 {
+    VIRTUAL_WIDTH
+}
+
+pub fn height() -> f32 {
+    VIRTUAL_HEIGHT
+}
+
+/// Where a child rectangle sits inside its parent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Anchor {
+    Top,
+    Left,
+    Center,
+    Right,
+    Bottom,
+}
+
+/// A rectangle on the virtual canvas.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rect {
+    pub x: f32,
+    pub y: f32,
+    pub w: f32,
+    pub h: f32,
+}
+
+impl Rect {
+    pub fn new(x: f32, y: f32, w: f32, h: f32) -> Rect {
+        Rect { x, y, w, h }
+    }
+
+    /// The whole virtual canvas.
+    pub fn screen() -> Rect {
+        Rect::new(0.0, 0.0, VIRTUAL_WIDTH, VIRTUAL_HEIGHT)
+    }
+
+    /// A `w` by `h` rectangle placed at `anchor` inside this one.
+    pub fn anchored(&self, anchor: Anchor, w: f32, h: f32) -> Rect {
+        let x = match anchor {
+            Anchor::Left => self.x,
+            Anchor::Top | Anchor::Center | Anchor::Bottom => self.x + (self.w - w) / 2.0,
+            Anchor::Right => self.x + self.w - w,
+        };
+        let y = match anchor {
+            Anchor::Top => self.y,
+            Anchor::Left | Anchor::Center | Anchor::Right => self.y + (self.h - h) / 2.0,
+            Anchor::Bottom => self.y + self.h - h,
+        };
+        Rect::new(x, y, w, h)
+    }
+
+    pub fn offset(&self, dx: f32, dy: f32) -> Rect {
+        Rect::new(self.x + dx, self.y + dy, self.w, self.h)
+    }
+
+    /// Shrinks the rectangle by `padding` on every side.
+    pub fn pad(&self, padding: f32) -> Rect {
+        Rect::new(self.x + padding, self.y + padding,
+                  (self.w - 2.0 * padding).max(0.0), (self.h - 2.0 * padding).max(0.0))
+    }
+
+    /// Splits the rectangle into `count` rows of equal height.
+    pub fn rows(&self, count: usize, gap: f32) -> Vec<Rect> {
+        let h = (self.h - gap * (count as f32 - 1.0)) / count as f32;
+        (0..count)
+            .map(|i| Rect::new(self.x, self.y + i as f32 * (h + gap), self.w, h))
+            .collect()
+    }
+
+    /// Splits the rectangle into `count` columns of equal width.
+    pub fn columns(&self, count: usize, gap: f32) -> Vec<Rect> {
+        let w = (self.w - gap * (count as f32 - 1.0)) / count as f32;
+        (0..count)
+            .map(|i| Rect::new(self.x + i as f32 * (w + gap), self.y, w, self.h))
+            .collect()
+    }
+
+    /// Cells of a `rows` by `columns` grid, in reading order.
+    pub fn grid(&self, rows: usize, columns: usize, gap: f32) -> Vec<Rect> {
+        self.rows(rows, gap).iter()
+            .flat_map(|row| row.columns(columns, gap))
+            .collect()
+    }
+
+    pub fn center(&self) -> (f32, f32) {
+        (self.x + self.w / 2.0, self.y + self.h / 2.0)
+    }
+
+    pub fn contains(&self, point: (f32, f32)) -> bool {
+        point.0 >= self.x && point.0 <= self.x + self.w
+            && point.1 >= self.y && point.1 <= self.y + self.h
+    }
+}
+
+/// Switches between fullscreen and a window of `size`.
+pub fn set_window(fullscreen: bool, size: (u32, u32)) {
+    set_fullscreen(fullscreen);
+    if !fullscreen {
+        request_new_screen_size(size.0 as f32, size.1 as f32);
+    }
+}
+
+/// How the virtual canvas maps onto the window, in logical pixels.
+struct Viewport {
+    scale: f32,
+    x: f32,
+    y: f32,
+}
+
+fn viewport() -> Viewport {
+    let scale = (screen_width() / VIRTUAL_WIDTH).min(screen_height() / VIRTUAL_HEIGHT);
+    Viewport {
+        scale,
+        x: (screen_width() - VIRTUAL_WIDTH * scale) / 2.0,
+        y: (screen_height() - VIRTUAL_HEIGHT * scale) / 2.0,
+    }
+}
+
+/// Points drawing at the virtual canvas for this frame.
+pub fn begin_frame() {
+    clear_background(BLACK);
+    set_offset(0.0, 0.0);
+}
+
+thread_local! {
+    static OFFSET: Cell<(f32, f32)> = const { Cell::new((0.0, 0.0)) };
+}
+
+/// Shifts everything drawn after this call by `(x, y)` on the virtual
+/// canvas, e.g. to slide a whole scene in. Mouse positions are not shifted.
+pub fn set_offset(x: f32, y: f32) {
+    OFFSET.with(|offset| offset.set((x, y)));
+    let view = viewport();
+    let dpi = screen_dpi_scale();
+    let mut camera = Camera2D::from_display_rect(macroquad::math::Rect::new(
+        -x, -y, VIRTUAL_WIDTH, VIRTUAL_HEIGHT
+    ));
+    camera.viewport = Some((
+        (view.x * dpi) as i32,
+        (view.y * dpi) as i32,
+        (VIRTUAL_WIDTH * view.scale * dpi) as i32,
+        (VIRTUAL_HEIGHT * view.scale * dpi) as i32,
+    ));
+    set_camera(&camera);
+}
+
+/// The shift set by the last `set_offset`, so a scene can shake on top of
+/// a slide without undoing it.
+pub fn offset() -> (f32, f32) {
+    OFFSET.with(Cell::get)
+}
+
+/// Covers whatever the canvas doesn't fill with black bars.
+pub fn end_frame() {
+    let view = viewport();
+    set_default_camera();
+    if view.x > 0.0 {
+        draw_rectangle(0.0, 0.0, view.x, screen_height(), BLACK);
+        draw_rectangle(screen_width() - view.x, 0.0, view.x, screen_height(), BLACK);
+    }
+    if view.y > 0.0 {
+        draw_rectangle(0.0, 0.0, screen_width(), view.y, BLACK);
+        draw_rectangle(0.0, screen_height() - view.y, screen_width(), view.y, BLACK);
+    }
+}
+
+/// Converts a window position in logical pixels to the virtual canvas.
+pub fn to_canvas(x: f32, y: f32) -> (f32, f32) {
+    let view = viewport();
+    ((x - view.x) / view.scale, (y - view.y) / view.scale)
+}
+
+/// Fills the whole virtual canvas. Scenes use this instead of
+/// `clear_background` so that two of them can share the screen during a
+/// transition.
+pub fn clear(color: Color) {
+    draw_rectangle(0.0, 0.0, VIRTUAL_WIDTH, VIRTUAL_HEIGHT, color);
+}
+
+/// Draws `text` horizontally centered on `center_x` with its baseline at `y`.
+pub fn draw_centered_text(text: &str, center_x: f32, y: f32, font_size: f32, color: Color) {
+    let text_dim = theme::measure_text(text, font_size as u16);
+    theme::draw_text(text, center_x - text_dim.width / 2.0, y, font_size, color);
+}
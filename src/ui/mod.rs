@@ -1,3 +1,15 @@
+pub mod anim;
 pub mod button;
-pub mod screen;
 pub mod tile;
+pub mod text_input;
+pub mod toast;
+pub mod focus;
+pub mod layout;
+pub mod theme;
+pub mod widget;
+pub mod label;
+pub mod panel;
+pub mod pointer;
+pub mod toggle;
+pub mod slider;
+pub mod modal;
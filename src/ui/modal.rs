@@ -0,0 +1,99 @@
+use macroquad::prelude::*;
+
+use crate::input;
+use crate::ui::button::Button;
+use crate::ui::label::{self, Label};
+use crate::ui::layout::{Anchor, Rect};
+use crate::ui::panel::Panel;
+use crate::ui::theme;
+use crate::ui::widget::{self, Widget};
+
+/// A dialog drawn over the whole screen. While it is open it owns focus
+/// navigation, so the screen underneath should skip its own widgets.
+pub struct Modal {
+    panel: Panel,
+    title: Label,
+    message: String,
+    buttons: Vec<Button>,
+    open: bool,
+}
+
+impl Modal {
+    /// A dialog with one button per entry in `choices`, left to right.
+    pub fn new(title: &str, message: &str, choices: &[&str]) -> Modal {
+        let theme = theme::current();
+        let panel_rect = Rect::screen().anchored(Anchor::Center, 640.0, 300.0);
+        let button_row = panel_rect.pad(25.0).anchored(Anchor::Bottom, 590.0, 50.0);
+        let buttons = button_row.columns(choices.len().max(1), 20.0).into_iter()
+            .zip(choices)
+            .map(|(rect, choice)| Button::from_rect(rect, theme.button, choice.to_string(), 30.0))
+            .collect();
+
+        Modal {
+            panel: Panel::new(panel_rect).with_border(theme.panel_text),
+            title: Label::new(panel_rect.anchored(Anchor::Top, panel_rect.w, 80.0), title, theme.title_font_size)
+                .with_color(theme.panel_text),
+            message: message.to_string(),
+            buttons,
+            open: false,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Handles focus and clicks for the dialog's buttons. Returns the index
+    /// of the choice picked this frame, which also closes the dialog. The
+    /// back key picks the last choice.
+    pub fn update(&mut self) -> Option<usize> {
+        if !self.open {
+            return None;
+        }
+        let mut focusable: Vec<&mut dyn Widget> = self.buttons.iter_mut()
+            .map(|button| button as &mut dyn Widget)
+            .collect();
+        widget::focus(&mut focusable);
+
+        let mut picked = None;
+        for (i, button) in self.buttons.iter_mut().enumerate() {
+            if button.update() {
+                picked = Some(i);
+            }
+        }
+        if picked.is_none() && input::is_back_pressed() {
+            picked = self.buttons.len().checked_sub(1);
+        }
+        if picked.is_some() {
+            self.open = false;
+        }
+        picked
+    }
+}
+
+impl Widget for Modal {
+    fn rect(&self) -> Rect {
+        self.panel.rect()
+    }
+
+    fn draw(&self) {
+        if !self.open {
+            return;
+        }
+        let theme = theme::current();
+        let screen = Rect::screen();
+        draw_rectangle(screen.x, screen.y, screen.w, screen.h, theme.overlay);
+
+        self.panel.draw();
+        self.title.draw();
+        let message = self.panel.rect().pad(25.0).offset(0.0, 20.0);
+        label::draw_aligned(&self.message, message, Anchor::Center, theme.font_size, theme.panel_text);
+        for button in &self.buttons {
+            button.draw();
+        }
+    }
+}
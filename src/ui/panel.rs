@@ -0,0 +1,41 @@
+use macroquad::prelude::*;
+
+use crate::ui::layout::Rect;
+use crate::ui::theme;
+use crate::ui::widget::Widget;
+
+/// A filled background box that other widgets are laid out on.
+pub struct Panel {
+    rect: Rect,
+    color: Color,
+    border: Option<Color>,
+}
+
+impl Panel {
+    pub fn new(rect: Rect) -> Panel {
+        Panel {
+            rect,
+            color: theme::current().panel,
+            border: None,
+        }
+    }
+
+    pub fn with_border(mut self, color: Color) -> Panel {
+        self.border = Some(color);
+        self
+    }
+}
+
+impl Widget for Panel {
+    fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    fn draw(&self) {
+        let rect = self.rect;
+        theme::draw_rounded_rect(rect.x, rect.y, rect.w, rect.h, theme::current().corner_radius, self.color);
+        if let Some(border) = self.border {
+            draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, 3.0, border);
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use macroquad::prelude::*;
+
+use crate::ui::layout;
+
+// The mouse and the touchscreen as a single pointer on the virtual canvas.
+// Only the first finger counts, so a second one resting on the screen
+// doesn't fight the first over buttons.
+
+fn touch() -> Option<Touch> {
+    touches().into_iter().next()
+}
+
+/// Where the pointer is on the virtual canvas.
+pub fn position() -> (f32, f32) {
+    match touch() {
+        Some(touch) => layout::to_canvas(touch.position.x, touch.position.y),
+        None => {
+            let (x, y) = mouse_position();
+            layout::to_canvas(x, y)
+        }
+    }
+}
+
+/// Whether the left button or a finger went down this frame.
+pub fn is_pressed() -> bool {
+    is_mouse_button_pressed(MouseButton::Left)
+        || touch().is_some_and(|touch| touch.phase == TouchPhase::Started)
+}
+
+pub fn is_down() -> bool {
+    is_mouse_button_down(MouseButton::Left)
+        || touch().is_some_and(|touch| {
+            matches!(touch.phase, TouchPhase::Started | TouchPhase::Stationary | TouchPhase::Moved)
+        })
+}
+
+/// Whether the pointer is over `rect`. A finger only hovers while it's
+/// touching the screen.
+pub fn is_over(rect: layout::Rect) -> bool {
+    rect.contains(position())
+}
@@ -0,0 +1,109 @@
+use macroquad::prelude::*;
+
+use crate::input::{self, Action};
+use crate::ui::label;
+use crate::ui::layout::{Anchor, Rect};
+use crate::ui::pointer;
+use crate::ui::theme;
+use crate::ui::widget::{FocusSlot, Widget};
+
+/// A labelled value between `min` and `max`. Drag the handle with the
+/// mouse, or press left and right while it has focus.
+pub struct Slider {
+    rect: Rect,
+    label: String,
+    value: f32,
+    min: f32,
+    max: f32,
+    step: f32,
+    dragging: bool,
+    focus: FocusSlot,
+}
+
+impl Slider {
+    pub fn new(rect: Rect, label: &str, value: f32, min: f32, max: f32, step: f32) -> Slider {
+        Slider {
+            rect,
+            label: label.to_string(),
+            value: value.clamp(min, max),
+            min,
+            max,
+            step,
+            dragging: false,
+            focus: FocusSlot::default(),
+        }
+    }
+
+    /// The label takes the left third of the slider and the track the rest.
+    fn track(&self) -> Rect {
+        let columns = self.rect.pad(10.0).columns(3, 0.0);
+        let track = Rect::new(columns[1].x, columns[1].y, columns[1].w * 2.0, columns[1].h);
+        track.anchored(Anchor::Center, track.w - 20.0, 8.0)
+    }
+
+    /// Moves the value with the mouse or cursor keys. Returns `true` if it changed.
+    pub fn update(&mut self) -> bool {
+        let old_value = self.value;
+        let track = self.track();
+        let mouse = pointer::position();
+
+        if pointer::is_pressed() {
+            self.dragging = self.rect.contains(mouse);
+        } else if !pointer::is_down() {
+            self.dragging = false;
+        }
+        if self.dragging {
+            let t = ((mouse.0 - track.x) / track.w).clamp(0.0, 1.0);
+            self.set_value(self.min + t * (self.max - self.min));
+        }
+
+        if self.focus.is_focused() {
+            if input::is_pressed(Action::CursorLeft) {
+                self.set_value(self.value - self.step);
+            } else if input::is_pressed(Action::CursorRight) {
+                self.set_value(self.value + self.step);
+            }
+        }
+        self.value != old_value
+    }
+
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// Snaps `value` to the slider's step and range.
+    pub fn set_value(&mut self, value: f32) {
+        let steps = ((value - self.min) / self.step).round();
+        self.value = (self.min + steps * self.step).clamp(self.min, self.max);
+    }
+}
+
+impl Widget for Slider {
+    fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    fn draw(&self) {
+        let theme = theme::current();
+        let rect = self.rect;
+        draw_rectangle(rect.x, rect.y, rect.w, rect.h, theme.panel);
+        if self.focus.is_focused() {
+            draw_rectangle_lines(rect.x - 5.0, rect.y - 5.0, rect.w + 10.0, rect.h + 10.0, 6.0, theme.focus);
+        }
+
+        let font_size = rect.h * 0.6;
+        let text = format!("{}: {}", self.label, self.value);
+        label::draw_aligned(&text, rect.pad(10.0), Anchor::Left, font_size, theme.panel_text);
+
+        let track = self.track();
+        let t = (self.value - self.min) / (self.max - self.min);
+        draw_rectangle(track.x, track.y, track.w, track.h, theme.disabled);
+        draw_rectangle(track.x, track.y, track.w * t, track.h, theme.accent);
+        let handle = Rect::new(track.x + track.w * t - 8.0, rect.y + 8.0, 16.0, rect.h - 16.0);
+        draw_rectangle(handle.x, handle.y, handle.w, handle.h, theme.panel_text);
+    }
+
+    fn focus_slot(&mut self) -> Option<&mut FocusSlot> {
+        Some(&mut self.focus)
+    }
+}
@@ -0,0 +1,66 @@
+use macroquad::prelude::*;
+
+use crate::input;
+use crate::ui::label;
+use crate::ui::layout::{Anchor, Rect};
+use crate::ui::theme;
+use crate::ui::widget::Widget;
+
+pub struct TextInput {
+    text: String,
+    max_len: usize,
+    rect: Rect,
+}
+
+impl TextInput {
+    pub fn new(max_len: usize) -> TextInput {
+        TextInput {
+            text: String::new(),
+            max_len,
+            rect: Rect::new(0.0, 0.0, 300.0, 50.0),
+        }
+    }
+
+    /// Reads this frame's typed characters and backspaces into the input.
+    pub fn update(&mut self) {
+        for c in input::typed_chars() {
+            if (c.is_alphanumeric() || c == ' ' || c == '-' || c == '_')
+                && self.text.chars().count() < self.max_len {
+                self.text.push(c);
+            }
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            self.text.pop();
+        }
+    }
+
+    pub fn set_rect(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl Widget for TextInput {
+    fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    fn draw(&self) {
+        let theme = theme::current();
+        let rect = self.rect;
+        draw_rectangle(rect.x, rect.y, rect.w, rect.h, theme.panel);
+        draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, 3.0, theme.panel_text);
+
+        // The caret hangs off the end so the text doesn't jump as it blinks.
+        let font_size = rect.h * 0.7;
+        let text_dim = theme::measure_text(&self.text, font_size as u16);
+        label::draw_aligned(&self.text, rect, Anchor::Center, font_size, theme.panel_text);
+        if (get_time() * 2.0) as i64 % 2 == 0 {
+            let caret = rect.anchored(Anchor::Center, text_dim.width, rect.h).offset(text_dim.width, 0.0);
+            label::draw_aligned("|", caret, Anchor::Left, font_size, theme.panel_text);
+        }
+    }
+}
@@ -0,0 +1,323 @@
+use std::cell::{Cell, RefCell};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use macroquad::prelude::*;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use crate::paths;
+use crate::unlocks::TileSkin;
+
+/// Themes that ship with the game. Theme files in the config directory's
+/// `themes` folder are listed after these and can override them by name.
+const BUILT_IN: [&str; 4] = [
+    include_str!("../themes/classic.json"),
+    include_str!("../themes/dark.json"),
+    include_str!("../themes/high_contrast.json"),
+    include_str!("../themes/colorblind.json"),
+];
+
+pub const DEFAULT_THEME: &str = "Classic";
+
+/// Colors, fonts and shapes shared by every screen. Theme files only need
+/// the fields they change; everything else comes from the classic theme.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    pub name: String,
+
+    #[serde(with = "hex_color")]
+    pub background: Color,
+    #[serde(with = "hex_color")]
+    pub panel: Color,
+    #[serde(with = "hex_color")]
+    pub text: Color,
+    #[serde(with = "hex_color")]
+    pub panel_text: Color,
+    #[serde(with = "hex_color")]
+    pub button: Color,
+    #[serde(with = "hex_color")]
+    pub button_text: Color,
+    #[serde(with = "hex_color")]
+    pub focus: Color,
+    #[serde(with = "hex_color")]
+    pub disabled: Color,
+    #[serde(with = "hex_color")]
+    pub accent: Color,
+    /// Headings and other text that should stand out on a panel.
+    #[serde(with = "hex_color")]
+    pub highlight: Color,
+    #[serde(with = "hex_color")]
+    pub success: Color,
+    #[serde(with = "hex_color")]
+    pub danger: Color,
+    #[serde(with = "hex_color")]
+    pub overlay: Color,
+    #[serde(with = "hex_color")]
+    pub win_background: Color,
+    #[serde(with = "hex_color")]
+    pub lose_background: Color,
+
+    /// The game screen's HUD boxes and action buttons.
+    #[serde(with = "hex_color")]
+    pub target_panel: Color,
+    #[serde(with = "hex_color")]
+    pub target_text: Color,
+    #[serde(with = "hex_color")]
+    pub words_panel: Color,
+    #[serde(with = "hex_color")]
+    pub discards_panel: Color,
+    #[serde(with = "hex_color")]
+    pub play_button: Color,
+    #[serde(with = "hex_color")]
+    pub hint_button: Color,
+    #[serde(with = "hex_color")]
+    pub shuffle_button: Color,
+
+    /// Tiles with the classic skin use this color; the other skins keep
+    /// their own.
+    #[serde(with = "hex_color")]
+    pub tile: Color,
+    #[serde(with = "hex_color")]
+    pub tile_text: Color,
+    pub tile_radius: f32,
+    pub corner_radius: f32,
+
+    /// A TTF file, absolute or relative to the config directory's `themes`
+    /// folder. The built-in font is used when it's missing or fails to load.
+    pub font: Option<String>,
+    /// Multiplies every font size, for themes with bigger or smaller text.
+    pub text_scale: f32,
+    pub font_size: f32,
+    pub title_font_size: f32,
+
+    /// How much hovered and pressed widgets darken their color.
+    pub hover_shade: f32,
+    pub pressed_shade: f32,
+}
+
+impl Default for Theme {
+    fn default() -> Theme {
+        Theme {
+            name: DEFAULT_THEME.to_string(),
+            background: DARKGRAY,
+            panel: WHITE,
+            text: WHITE,
+            panel_text: BLACK,
+            button: GOLD,
+            button_text: BLACK,
+            focus: ORANGE,
+            disabled: GRAY,
+            accent: LIME,
+            highlight: BLUE,
+            success: DARKGREEN,
+            danger: RED,
+            overlay: Color::new(0.0, 0.0, 0.0, 0.6),
+            win_background: GREEN,
+            lose_background: RED,
+            target_panel: BLACK,
+            target_text: WHITE,
+            words_panel: SKYBLUE,
+            discards_panel: Color::new(1.0, 127.0 / 255.0, 80.0 / 255.0, 1.0),
+            play_button: GREEN,
+            hint_button: SKYBLUE,
+            shuffle_button: YELLOW,
+            tile: WHITE,
+            tile_text: BLACK,
+            tile_radius: 0.0,
+            corner_radius: 0.0,
+            font: None,
+            text_scale: 1.0,
+            font_size: 30.0,
+            title_font_size: 60.0,
+            hover_shade: 0.85,
+            pressed_shade: 0.7,
+        }
+    }
+}
+
+impl Theme {
+    pub fn shade(color: Color, factor: f32) -> Color {
+        Color::new(color.r * factor, color.g * factor, color.b * factor, color.a)
+    }
+
+    /// Swaps the colors that tell good from bad for Okabe-Ito ones, which
+    /// stay apart with red-green color blindness. Layout colors are kept.
+    fn with_colorblind_palette(mut self) -> Theme {
+        let blue = Color::from_rgba(0x00, 0x72, 0xb2, 255);
+        let sky = Color::from_rgba(0x56, 0xb4, 0xe9, 255);
+        let green = Color::from_rgba(0x00, 0x9e, 0x73, 255);
+        let orange = Color::from_rgba(0xe6, 0x9f, 0x00, 255);
+        let vermillion = Color::from_rgba(0xd5, 0x5e, 0x00, 255);
+        self.accent = green;
+        self.success = green;
+        self.highlight = blue;
+        self.danger = vermillion;
+        self.win_background = sky;
+        self.lose_background = vermillion;
+        self.words_panel = sky;
+        self.discards_panel = orange;
+        self.play_button = green;
+        self.hint_button = sky;
+        self
+    }
+}
+
+static THEME: Lazy<Mutex<Arc<Theme>>> = Lazy::new(|| Mutex::new(Arc::new(Theme::default())));
+
+thread_local! {
+    /// The current theme's font. macroquad fonts live on the render thread.
+    static FONT: RefCell<Option<Font>> = const { RefCell::new(None) };
+    /// The current theme's text scale, kept here so drawing text doesn't
+    /// need the theme.
+    static TEXT_SCALE: Cell<f32> = const { Cell::new(1.0) };
+}
+
+/// The theme in use. Cheap to call, as the theme itself isn't copied.
+pub fn current() -> Arc<Theme> {
+    THEME.lock().unwrap().clone()
+}
+
+/// The tile color of a skin. Classic tiles follow the theme.
+pub fn skin_color(skin: TileSkin) -> Color {
+    match skin {
+        TileSkin::Classic => current().tile,
+        TileSkin::Gold => GOLD,
+        TileSkin::Neon => Color::new(0.2, 1.0, 0.9, 1.0),
+        TileSkin::Mint => Color::new(0.7, 1.0, 0.8, 1.0),
+    }
+}
+
+fn themes_dir() -> PathBuf {
+    paths::config_dir().join("themes")
+}
+
+/// Every theme that can be picked: the built-in ones, then the player's.
+pub fn available() -> Vec<Theme> {
+    let mut themes: Vec<Theme> = BUILT_IN.iter()
+        .filter_map(|contents| match serde_json::from_str(contents) {
+            Ok(theme) => Some(theme),
+            Err(e) => {
+                tracing::error!("Failed to parse built-in theme: {}", e);
+                None
+            }
+        })
+        .collect();
+
+    let entries = fs::read_dir(themes_dir()).into_iter().flatten().flatten();
+    for path in entries.map(|entry| entry.path()) {
+        if path.extension().is_none_or(|extension| extension != "json") {
+            continue;
+        }
+        let theme: Theme = match fs::read_to_string(&path).map_err(|e| e.to_string())
+            .and_then(|contents| serde_json::from_str(&contents).map_err(|e| e.to_string())) {
+            Ok(theme) => theme,
+            Err(e) => {
+                tracing::warn!(path = %path.display(), "Failed to load theme: {}", e);
+                continue;
+            }
+        };
+        themes.retain(|existing| existing.name != theme.name);
+        themes.push(theme);
+    }
+    themes
+}
+
+/// Switches to the theme called `name`, falling back to the classic theme
+/// when there's no such theme. `colorblind` swaps in the colorblind-safe
+/// status colors on top of it.
+pub fn apply(name: &str, colorblind: bool) {
+    let mut theme = available().into_iter()
+        .find(|theme| theme.name == name)
+        .unwrap_or_default();
+    if colorblind {
+        theme = theme.with_colorblind_palette();
+    }
+    let font = theme.font.as_deref().and_then(|font| load_font(&themes_dir(), font));
+    FONT.with(|current| *current.borrow_mut() = font);
+    TEXT_SCALE.with(|scale| scale.set(theme.text_scale));
+    *THEME.lock().unwrap() = Arc::new(theme);
+}
+
+fn load_font(dir: &Path, font: &str) -> Option<Font> {
+    let path = dir.join(font);
+    let result = fs::read(&path).map_err(|e| e.to_string())
+        .and_then(|bytes| load_ttf_font_from_bytes(&bytes).map_err(|e| e.to_string()));
+    match result {
+        Ok(font) => Some(font),
+        Err(e) => {
+            tracing::warn!(path = %path.display(), "Failed to load font, using the default font: {}", e);
+            None
+        }
+    }
+}
+
+/// `draw_text` in the theme's font and text scale.
+pub fn draw_text(text: &str, x: f32, y: f32, font_size: f32, color: Color) {
+    let scale = TEXT_SCALE.with(Cell::get);
+    FONT.with(|font| {
+        draw_text_ex(text, x, y, TextParams {
+            font: font.borrow().as_ref(),
+            font_size: (font_size * scale) as u16,
+            color,
+            ..Default::default()
+        });
+    });
+}
+
+/// `measure_text` in the theme's font and text scale.
+pub fn measure_text(text: &str, font_size: u16) -> TextDimensions {
+    let scale = TEXT_SCALE.with(Cell::get);
+    FONT.with(|font| {
+        macroquad::text::measure_text(text, font.borrow().as_ref(), (font_size as f32 * scale) as u16, 1.0)
+    })
+}
+
+/// A filled rectangle with rounded corners. Only meant for opaque colors,
+/// since the corner pieces overlap.
+pub fn draw_rounded_rect(x: f32, y: f32, w: f32, h: f32, radius: f32, color: Color) {
+    let radius = radius.min(w / 2.0).min(h / 2.0);
+    if radius <= 0.0 {
+        draw_rectangle(x, y, w, h, color);
+        return;
+    }
+    draw_rectangle(x + radius, y, w - 2.0 * radius, h, color);
+    draw_rectangle(x, y + radius, w, h - 2.0 * radius, color);
+    for (cx, cy) in [(x + radius, y + radius), (x + w - radius, y + radius),
+                     (x + radius, y + h - radius), (x + w - radius, y + h - radius)] {
+        draw_circle(cx, cy, radius, color);
+    }
+}
+
+/// Colors in theme files are written as `#rrggbb` or `#rrggbbaa`.
+mod hex_color {
+    use macroquad::prelude::Color;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+        let [r, g, b, a]: [u8; 4] = (*color).into();
+        let hex = if a == 255 {
+            format!("#{:02x}{:02x}{:02x}", r, g, b)
+        } else {
+            format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)
+        };
+        serializer.serialize_str(&hex)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        parse(&hex).ok_or_else(|| serde::de::Error::custom(format!("invalid color: {}", hex)))
+    }
+
+    fn parse(hex: &str) -> Option<Color> {
+        let digits = hex.strip_prefix('#')?;
+        if !digits.is_ascii() || (digits.len() != 6 && digits.len() != 8) {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).ok();
+        let alpha = if digits.len() == 8 { channel(6)? } else { 255 };
+        Some(Color::from_rgba(channel(0)?, channel(2)?, channel(4)?, alpha))
+    }
+}
@@ -1,29 +1,89 @@
 use macroquad::prelude::*;
 
 use crate::ui;
+use crate::ui::anim::Tween;
+use crate::ui::layout::Rect;
+use crate::ui::theme;
+
+/// How long a tile takes to slide to a new spot at normal animation speed.
+const SLIDE_SECONDS: f32 = 0.25;
 
 pub struct Tile {
     letter: char,
-    button: ui::button::Button
+    button: ui::button::Button,
+    used: bool,
+    tween: Option<Tween>,
 }
 
 impl Tile {
-    pub fn new(letter: char, x: f32, y: f32) -> Tile {
-        let button = ui::button::Button::new(x, y, 
-                                             100.0, 100.0,
-                                             WHITE, letter.to_string(), 40.0);
+    pub fn new(letter: char, rect: Rect, color: Color) -> Tile {
         Tile {
             letter,
-            button,
+            button: tile_button(letter, rect, color),
+            used: false,
+            tween: None,
         }
     }
-    
+
+    /// Slides the tile from where it is now to `rect`, starting after
+    /// `delay` seconds. The tile stays clickable while it moves.
+    pub fn slide_to(&mut self, rect: Rect, delay: f32) {
+        self.tween = Some(Tween::new(self.button.rect(), rect, SLIDE_SECONDS).with_delay(delay));
+    }
+
+    /// Moves the tile straight to `rect`, e.g. under a dragging finger.
+    pub fn place(&mut self, rect: Rect) {
+        self.tween = None;
+        self.button.set_rect(rect);
+    }
+
+    /// Advances the tile's slide, if it has one.
+    pub fn update(&mut self, dt: f32) {
+        if let Some(tween) = self.tween.as_mut() {
+            tween.update(dt);
+            self.button.set_rect(tween.rect());
+            if tween.is_done() {
+                self.tween = None;
+            }
+        }
+    }
+
+    pub fn is_moving(&self) -> bool {
+        self.tween.is_some()
+    }
+
+    pub fn rect(&self) -> Rect {
+        self.button.rect()
+    }
+
+    /// Redraws the tile in `color` and the current theme's tile style.
+    pub fn set_color(&mut self, color: Color) {
+        self.button = tile_button(self.letter, self.button.rect(), color);
+    }
+
     pub fn get_button(&mut self) -> &mut ui::button::Button {
         &mut self.button
     }
-    pub fn draw(&self) { self.button.draw(); }
-    pub fn is_clicked(&self) -> bool { self.button.is_clicked() }
+    pub fn draw(&self) {
+        self.button.draw();
+        if self.used {
+            let rect = self.button.rect();
+            draw_rectangle(rect.x, rect.y, rect.w, rect.h, theme::current().overlay);
+        }
+    }
     pub fn get_letter(&self) -> char {
         self.letter
     }
+    pub fn is_used(&self) -> bool {
+        self.used
+    }
+    pub fn set_used(&mut self, used: bool) {
+        self.used = used;
+    }
+}
+
+fn tile_button(letter: char, rect: Rect, color: Color) -> ui::button::Button {
+    let theme = theme::current();
+    ui::button::Button::from_rect(rect, color, letter.to_string(), 40.0)
+        .with_style(theme.tile_radius, theme.tile_text)
 }
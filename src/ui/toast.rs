@@ -0,0 +1,45 @@
+use std::sync::Mutex;
+
+use macroquad::prelude::*;
+
+use crate::ui::layout;
+use crate::ui::theme;
+
+const TOAST_SECONDS: f32 = 3.0;
+const MAX_VISIBLE: usize = 3;
+
+struct Toast {
+    text: String,
+    time_left: f32,
+}
+
+static TOASTS: Mutex<Vec<Toast>> = Mutex::new(Vec::new());
+
+/// Queues a short notification shown in the top-right corner of any screen.
+pub fn show(text: String) {
+    TOASTS.lock().unwrap().push(Toast {
+        text,
+        time_left: TOAST_SECONDS,
+    });
+}
+
+/// Draws the oldest toasts on top of the current screen and ages them.
+pub fn draw() {
+    let mut toasts = TOASTS.lock().unwrap();
+    let frame_time = get_frame_time();
+
+    let mut y = 20.0;
+    for toast in toasts.iter_mut().take(MAX_VISIBLE) {
+        let text_dim = theme::measure_text(&toast.text, 25);
+        let width = text_dim.width + 40.0;
+        let x = layout::width() - width - 20.0;
+        let alpha = toast.time_left.min(0.5) / 0.5;
+
+        draw_rectangle(x, y, width, 50.0, Color::new(0.0, 0.0, 0.0, 0.8 * alpha));
+        theme::draw_text(&toast.text, x + 20.0, y + 32.0, 25.0, Color::new(1.0, 0.85, 0.2, alpha));
+
+        toast.time_left -= frame_time;
+        y += 60.0;
+    }
+    toasts.retain(|toast| toast.time_left > 0.0);
+}
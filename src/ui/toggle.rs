@@ -0,0 +1,77 @@
+use macroquad::prelude::*;
+
+use crate::i18n::tr;
+use crate::ui::label;
+use crate::ui::layout::{Anchor, Rect};
+use crate::ui::pointer;
+use crate::ui::theme::{self, Theme};
+use crate::ui::widget::{FocusSlot, Widget};
+
+/// An on/off switch with its label on the left.
+pub struct Toggle {
+    rect: Rect,
+    label: String,
+    on: bool,
+    focus: FocusSlot,
+}
+
+impl Toggle {
+    pub fn new(rect: Rect, label: &str, on: bool) -> Toggle {
+        Toggle {
+            rect,
+            label: label.to_string(),
+            on,
+            focus: FocusSlot::default(),
+        }
+    }
+
+    /// Flips the switch when clicked or activated. Returns `true` if it changed.
+    pub fn update(&mut self) -> bool {
+        let clicked = pointer::is_pressed() && pointer::is_over(self.rect);
+        if clicked || self.focus.is_activated() {
+            self.on = !self.on;
+            return true;
+        }
+        false
+    }
+
+    pub fn is_on(&self) -> bool {
+        self.on
+    }
+
+    pub fn set_on(&mut self, on: bool) {
+        self.on = on;
+    }
+}
+
+impl Widget for Toggle {
+    fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    fn draw(&self) {
+        let theme = theme::current();
+        let rect = self.rect;
+        let background = if pointer::is_over(rect) {
+            Theme::shade(theme.panel, theme.hover_shade)
+        } else {
+            theme.panel
+        };
+        theme::draw_rounded_rect(rect.x, rect.y, rect.w, rect.h, theme.corner_radius, background);
+        if self.focus.is_focused() {
+            draw_rectangle_lines(rect.x - 5.0, rect.y - 5.0, rect.w + 10.0, rect.h + 10.0, 6.0, theme.focus);
+        }
+
+        let font_size = rect.h * 0.6;
+        label::draw_aligned(&self.label, rect.pad(10.0), Anchor::Left, font_size, theme.panel_text);
+
+        let switch = rect.pad(8.0).anchored(Anchor::Right, rect.h * 1.6, rect.h - 16.0);
+        let (color, text) = if self.on { (theme.accent, tr("common.on")) } else { (theme.disabled, tr("common.off")) };
+        theme::draw_rounded_rect(switch.x, switch.y, switch.w, switch.h, theme.corner_radius, color);
+        label::draw_aligned(text, switch, Anchor::Center, font_size * 0.8, theme.panel_text);
+    }
+
+    fn focus_slot(&mut self) -> Option<&mut FocusSlot> {
+        Some(&mut self.focus)
+    }
+}
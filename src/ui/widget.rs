@@ -0,0 +1,61 @@
+use crate::ui::focus;
+use crate::ui::layout::Rect;
+
+/// A piece of UI that a screen keeps between frames. Widgets hold their own
+/// state (text, hover, values); the screen decides where they go and what
+/// their events mean.
+pub trait Widget {
+    fn rect(&self) -> Rect;
+    fn draw(&self);
+
+    /// The widget's place in keyboard and controller navigation, or `None`
+    /// for widgets that can't take focus.
+    fn focus_slot(&mut self) -> Option<&mut FocusSlot> {
+        None
+    }
+}
+
+/// Which focus index a widget was given this frame by [`focus`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FocusSlot {
+    index: Option<usize>,
+}
+
+impl FocusSlot {
+    pub fn is_focused(&self) -> bool {
+        self.index.is_some_and(focus::is_focused)
+    }
+
+    pub fn is_activated(&self) -> bool {
+        self.index.is_some_and(focus::is_activated)
+    }
+}
+
+/// Runs this frame's focus navigation over `widgets`, numbering the
+/// focusable ones in the order given. Consecutive widgets at the same height
+/// share a row, so list them in reading order.
+pub fn focus(widgets: &mut [&mut dyn Widget]) {
+    let mut rows: Vec<usize> = Vec::new();
+    let mut row_y = None;
+    let mut index = 0;
+    for widget in widgets.iter_mut() {
+        let y = widget.rect().y;
+        let Some(slot) = widget.focus_slot() else {
+            continue;
+        };
+        slot.index = Some(index);
+        index += 1;
+        match rows.last_mut() {
+            Some(count) if row_y == Some(y) => *count += 1,
+            _ => rows.push(1),
+        }
+        row_y = Some(y);
+    }
+    focus::begin(&rows);
+}
+
+pub fn draw_all(widgets: &[&dyn Widget]) {
+    for widget in widgets {
+        widget.draw();
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{self, tr, tr_args};
+use crate::storage::{self, Area};
+
+/// The unlocked content the player has chosen for their next runs.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Loadout {
+    pub skin: TileSkin,
+    pub stake: Stake,
+    pub modifiers: Vec<Modifier>,
+}
+
+/// The achievements the player has earned, by id, and the loadout they
+/// picked. The window version tracks achievements; the terminal version
+/// plays with the loadout.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Progress {
+    pub earned: HashSet<String>,
+    pub loadout: Loadout,
+}
+
+const PROGRESS_FILE: &str = "achievements.json";
+
+impl Progress {
+    /// The saved progress, or none yet when there's no file or it can't be
+    /// read.
+    pub fn load() -> Progress {
+        storage::get().read(Area::Data, PROGRESS_FILE)
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let serialized = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize achievements: {}", e))?;
+        storage::get().write(Area::Data, PROGRESS_FILE, &serialized)
+            .map_err(|e| format!("Failed to write achievements file: {}", e))
+    }
+}
+
+/// Content that starts locked and is earned through achievements.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Unlock {
+    Skin(TileSkin),
+    Stake(Stake),
+    Modifier(Modifier),
+}
+
+impl Unlock {
+    pub fn name(&self) -> String {
+        match self {
+            Unlock::Skin(skin) => tr_args("unlock.skin", &[("name", &skin.name())]),
+            Unlock::Stake(stake) => tr_args("unlock.stake", &[("name", &stake.name())]),
+            Unlock::Modifier(modifier) => tr_args("unlock.modifier", &[("name", &modifier.name())]),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TileSkin {
+    #[default]
+    Classic,
+    Gold,
+    Neon,
+    Mint,
+}
+
+impl TileSkin {
+    pub const ALL: [TileSkin; 4] = [TileSkin::Classic, TileSkin::Gold, TileSkin::Neon, TileSkin::Mint];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TileSkin::Classic => tr("skin.classic"),
+            TileSkin::Gold => tr("skin.gold"),
+            TileSkin::Neon => tr("skin.neon"),
+            TileSkin::Mint => tr("skin.mint"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Stake {
+    #[default]
+    Normal,
+    Hard,
+    Brutal,
+}
+
+impl Stake {
+    pub const ALL: [Stake; 3] = [Stake::Normal, Stake::Hard, Stake::Brutal];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Stake::Normal => tr("stake.normal"),
+            Stake::Hard => tr("stake.hard"),
+            Stake::Brutal => tr("stake.brutal"),
+        }
+    }
+
+    /// Extra points added to every round's target.
+    pub fn target_bonus(&self) -> u64 {
+        match self {
+            Stake::Normal => 0,
+            Stake::Hard => 250,
+            Stake::Brutal => 500,
+        }
+    }
+
+    /// How many fewer words than the ruleset allows each round.
+    pub fn fewer_words(&self) -> u32 {
+        match self {
+            Stake::Brutal => 1,
+            _ => 0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Modifier {
+    /// Words of 7 or more letters score double.
+    LongWords,
+    /// Every rare letter in a word adds 50 points, e.g. Q, Z, X or J in English.
+    RareBonus,
+    /// One more discard per round.
+    ExtraDiscard,
+}
+
+impl Modifier {
+    pub const ALL: [Modifier; 3] = [Modifier::LongWords, Modifier::RareBonus, Modifier::ExtraDiscard];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Modifier::LongWords => tr("modifier.long_words"),
+            Modifier::RareBonus => tr("modifier.rare_bonus"),
+            Modifier::ExtraDiscard => tr("modifier.extra_discard"),
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            Modifier::LongWords => tr("modifier.long_words.description").to_string(),
+            Modifier::RareBonus => tr_args("modifier.rare_bonus.description",
+                                           &[("letters", &i18n::current().rare_letters())]),
+            Modifier::ExtraDiscard => tr("modifier.extra_discard.description").to_string(),
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! What the browser build needs from JavaScript: localStorage, the local
+//! time and random numbers. `web/word-quest.js` provides these functions to
+//! the wasm module through miniquad's plugin system.
+
+extern "C" {
+    /// Looks up `key` and returns the byte length of its UTF-8 value, or -1
+    /// when it isn't set. The value is kept for `wq_storage_take`.
+    fn wq_storage_get(key: *const u8, key_len: usize) -> i32;
+    /// Copies the value found by the last `wq_storage_get` into `buffer`.
+    fn wq_storage_take(buffer: *mut u8);
+    /// Returns false when the browser refuses, e.g. when storage is full.
+    fn wq_storage_set(key: *const u8, key_len: usize, value: *const u8, value_len: usize) -> bool;
+    fn wq_storage_remove(key: *const u8, key_len: usize);
+    /// Milliseconds since the epoch, shifted by the browser's time zone.
+    fn wq_local_time() -> f64;
+    fn wq_random_fill(buffer: *mut u8, len: usize);
+}
+
+pub fn local_storage_get(key: &str) -> Option<String> {
+    let len = unsafe { wq_storage_get(key.as_ptr(), key.len()) };
+    let len = usize::try_from(len).ok()?;
+    let mut buffer = vec![0; len];
+    unsafe { wq_storage_take(buffer.as_mut_ptr()) };
+    String::from_utf8(buffer).ok()
+}
+
+pub fn local_storage_set(key: &str, value: &str) -> Result<(), String> {
+    if unsafe { wq_storage_set(key.as_ptr(), key.len(), value.as_ptr(), value.len()) } {
+        Ok(())
+    } else {
+        Err("The browser refused to store it".to_string())
+    }
+}
+
+pub fn local_storage_remove(key: &str) {
+    unsafe { wq_storage_remove(key.as_ptr(), key.len()) };
+}
+
+/// The player's date and time, as chrono can't read the browser's clock
+/// without wasm-bindgen.
+pub fn local_now() -> chrono::NaiveDateTime {
+    let millis = unsafe { wq_local_time() } as i64;
+    chrono::DateTime::from_timestamp_millis(millis).unwrap_or_default().naive_utc()
+}
+
+fn fill_random(buffer: &mut [u8]) -> Result<(), getrandom::Error> {
+    unsafe { wq_random_fill(buffer.as_mut_ptr(), buffer.len()) };
+    Ok(())
+}
+
+// rand's `random()` gets its entropy from getrandom, which has no source of
+// its own on wasm32-unknown-unknown.
+getrandom::register_custom_getrandom!(fill_random);
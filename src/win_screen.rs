@@ -1,49 +1,62 @@
-use macroquad::color::{BLACK, GREEN, RED, WHITE};
-use macroquad::input::is_key_pressed;
-use macroquad::prelude::{clear_background, draw_text, screen_height, screen_width, KeyCode};
-use crate::ui;
+use macroquad::prelude::*;
 
-pub fn draw_screen() {
-    clear_background(GREEN);
+use crate::i18n::tr;
+use crate::leaderboard::PendingRun;
+use crate::leaderboard_screen::NameEntry;
+use crate::scene::{Context, Scene, Transition};
+use crate::title::TitleScreen;
+use crate::ui::button::Button;
+use crate::ui::label::Label;
+use crate::ui::layout::{self, Anchor, Rect};
+use crate::ui::theme;
+use crate::ui::widget::{self, Widget};
 
-    let text = "You Win!";
-    let font_size = 80;
-    let screen_width = screen_width();
-    let screen_height = screen_height();
+pub struct WinScreen {
+    title: Label,
+    name_entry: NameEntry,
+    play_again: Button,
+    quit: Button,
+}
+
+impl WinScreen {
+    pub fn new(run: PendingRun) -> WinScreen {
+        let theme = theme::current();
+        let buttons = Rect::screen().anchored(Anchor::Center, 250.0, 150.0)
+            .offset(0.0, 25.0)
+            .rows(2, 50.0);
+        WinScreen {
+            title: Label::new(Rect::new(0.0, layout::height() / 2.0 - 260.0, layout::width(), 80.0),
+                              tr("win.title"), 80.0)
+                .with_color(theme.panel_text),
+            name_entry: NameEntry::new(layout::height() / 2.0 - 140.0, run),
+            play_again: Button::from_rect(buttons[0], theme.panel, tr("common.play_again").to_string(), 40.0),
+            quit: Button::from_rect(buttons[1], theme.panel, tr("common.quit").to_string(), 40.0),
+        }
+    }
+}
 
-    draw_text(
-        text,
-        screen_width / 2.0 - (text.len() as f32 * font_size as f32 / 4.0),
-        screen_height / 2.0 - 100.0,
-        font_size as f32,
-        BLACK,
-    );
+impl Scene for WinScreen {
+    fn handle_input(&mut self, _ctx: &mut Context) -> Transition {
+        // The name prompt's Submit button comes first in focus order while it shows.
+        let mut focusable: Vec<&mut dyn Widget> = self.name_entry.focusable().into_iter().collect();
+        focusable.push(&mut self.play_again);
+        focusable.push(&mut self.quit);
+        widget::focus(&mut focusable);
 
-    let start_button = ui::button::Button::new(
-        macroquad::prelude::screen_width() / 2.0 - 125.0,
-        macroquad::prelude::screen_height() / 2.0 - 50.0,
-        200.0,
-        50.0,
-        WHITE,
-        "Play Again".to_string(),
-        40.0
-    );
-    start_button.draw();
-    if start_button.is_clicked() || is_key_pressed(KeyCode::Enter) {
-        ui::screen::ScreenManager::switch_screen(ui::screen::Screen::Title);
+        let entering_name = self.name_entry.update();
+        if self.play_again.update() || (!entering_name && is_key_pressed(KeyCode::Enter)) {
+            return Transition::Replace(Box::new(TitleScreen::new()));
+        }
+        if self.quit.update() {
+            return Transition::Quit;
+        }
+        Transition::None
     }
 
-    let start_button = ui::button::Button::new(
-        macroquad::prelude::screen_width() / 2.0 - 125.0,
-        macroquad::prelude::screen_height() / 2.0 + 50.0,
-        200.0,
-        50.0,
-        WHITE,
-        "Quit".to_string(),
-        40.0
-    );
-    start_button.draw();
-    if start_button.is_clicked() {
-        std::process::exit(0);
+    fn draw(&self, _ctx: &Context) {
+        layout::clear(theme::current().win_background);
+        self.title.draw();
+        self.name_entry.draw();
+        widget::draw_all(&[&self.play_again, &self.quit]);
     }
 }
@@ -0,0 +1,35 @@
+#!/bin/sh
+# Builds the browser version into web/dist, ready for any static file server:
+#
+#     web/build.sh && python3 -m http.server --directory web/dist
+#
+# Extra arguments go to cargo, e.g. `web/build.sh --features audio`.
+set -e
+cd "$(dirname "$0")/.."
+
+# The English dictionary isn't in the repository. It's the word list from
+# github.com/dwyl/english-words, fetched once and kept next to the others.
+ENGLISH_DICTIONARY=https://raw.githubusercontent.com/dwyl/english-words/master/words_dictionary.json
+if [ ! -e src/words_dictionary.json ]; then
+    echo "Fetching the English dictionary"
+    curl -fsSL -o src/words_dictionary.json.part "$ENGLISH_DICTIONARY"
+    mv src/words_dictionary.json.part src/words_dictionary.json
+fi
+
+cargo build --release --target wasm32-unknown-unknown --bin ci210-word-game "$@"
+
+rm -rf web/dist
+mkdir -p web/dist/src/dictionaries
+cp target/wasm32-unknown-unknown/release/ci210-word-game.wasm web/dist/word-quest.wasm
+cp web/index.html web/mq_js_bundle.js web/word-quest.js web/dist/
+
+# The game fetches dictionaries and sounds by the same paths it reads them
+# from on desktop, relative to the page.
+cp src/words_dictionary.json web/dist/src/
+for dictionary in src/dictionaries/*.json; do
+    [ -e "$dictionary" ] && cp "$dictionary" web/dist/src/dictionaries/
+done
+if [ -d assets ]; then
+    cp -r assets web/dist/
+fi
+echo "Built web/dist"
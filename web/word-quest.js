@@ -0,0 +1,66 @@
+// The browser side of src/web.rs: localStorage, the local time and random
+// numbers for the wasm module. Loaded after mq_js_bundle.js.
+
+(function () {
+    var encoder = new TextEncoder();
+    var decoder = new TextDecoder();
+    // The value found by the last wq_storage_get, until Rust takes it.
+    var pending = null;
+
+    function readString(ptr, len) {
+        return decoder.decode(new Uint8Array(wasm_memory.buffer, ptr, len));
+    }
+
+    miniquad_add_plugin({
+        name: "word_quest",
+        version: 1,
+        register_plugin: function (importObject) {
+            importObject.env.wq_storage_get = function (keyPtr, keyLen) {
+                var value = null;
+                try {
+                    value = window.localStorage.getItem(readString(keyPtr, keyLen));
+                } catch (e) {
+                    console.error("Word Quest: localStorage is unavailable", e);
+                }
+                if (value === null) {
+                    pending = null;
+                    return -1;
+                }
+                pending = encoder.encode(value);
+                return pending.length;
+            };
+            importObject.env.wq_storage_take = function (bufferPtr) {
+                new Uint8Array(wasm_memory.buffer, bufferPtr, pending.length).set(pending);
+                pending = null;
+            };
+            importObject.env.wq_storage_set = function (keyPtr, keyLen, valuePtr, valueLen) {
+                try {
+                    window.localStorage.setItem(readString(keyPtr, keyLen), readString(valuePtr, valueLen));
+                    return true;
+                } catch (e) {
+                    console.error("Word Quest: failed to save", e);
+                    return false;
+                }
+            };
+            importObject.env.wq_storage_remove = function (keyPtr, keyLen) {
+                try {
+                    window.localStorage.removeItem(readString(keyPtr, keyLen));
+                } catch (e) {
+                    console.error("Word Quest: localStorage is unavailable", e);
+                }
+            };
+            importObject.env.wq_local_time = function () {
+                var now = new Date();
+                return now.getTime() - now.getTimezoneOffset() * 60000;
+            };
+            importObject.env.wq_random_fill = function (bufferPtr, len) {
+                // getRandomValues takes at most 65536 bytes at a time.
+                for (var offset = 0; offset < len; offset += 65536) {
+                    var size = Math.min(65536, len - offset);
+                    crypto.getRandomValues(new Uint8Array(wasm_memory.buffer, bufferPtr + offset, size));
+                }
+            };
+        },
+        on_init: function () {},
+    });
+})();